@@ -0,0 +1,81 @@
+//! Generate the `yYYYY::day_XX` solution modules and the registry of implemented days from the
+//! `yYYYY/day_XX.rs` files found in the solutions directory (`src/solutions/` unless
+//! `solutions_dir` is set in `aoc.toml`), so new days are picked up without editing code.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Solutions directory set in `aoc.toml`, if any.
+fn configured_dir(root: &Path) -> Option<String> {
+    let config = root.join("aoc.toml");
+    println!("cargo:rerun-if-changed={}", config.display());
+    let config: toml::Value = fs::read_to_string(config).ok()?.parse().ok()?;
+    Some(config.get("solutions_dir")?.as_str()?.to_string())
+}
+
+/// Numbers of the entries of `dir` named `{prefix}N{suffix}`, sorted.
+fn numbered<T: std::str::FromStr + Ord>(
+    dir: &Path,
+    prefix: &str,
+    suffix: &str,
+) -> Vec<(T, String)> {
+    let mut entries: Vec<(T, String)> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().ok()?;
+            let n = name
+                .strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()?;
+            Some((n, name))
+        })
+        .collect();
+    entries.sort();
+    entries
+}
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let configured = configured_dir(&root).unwrap_or("src/solutions".into());
+    // Let the binary check that the configuration it runs with agrees.
+    println!("cargo:rustc-env=AOC_SOLUTIONS_DIR={configured}");
+    let dir = root.join(configured);
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut code = String::new();
+    let mut days = vec![];
+    for (year, name) in numbered::<u16>(&dir, "y", "") {
+        let year_dir = dir.join(&name);
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+        code += &format!("pub mod y{year} {{\n");
+        for (day, name) in numbered::<u8>(&year_dir, "day_", ".rs") {
+            if name.len() != "day_XX.rs".len() || !(1..=25).contains(&day) {
+                panic!(
+                    "`{}` is not a valid day, expected day_01.rs to day_25.rs",
+                    year_dir.join(&name).display()
+                );
+            }
+            code += &format!(
+                "    #[path = {:?}]\n    pub mod day_{day:0>2};\n",
+                year_dir.join(&name).display().to_string()
+            );
+            days.push((year, day));
+        }
+        code += "}\n";
+    }
+
+    code += "\n/// Days that have a solution implemented.\npub const DAYS: &[utils::Day] = &[\n";
+    for (year, day) in &days {
+        code += &format!(
+            "    utils::Day {{ year: {year}, day: {day}, \
+            parse: utils::parse::<y{year}::day_{day:0>2}::Puzzle> }},\n"
+        );
+    }
+    code += "];\n";
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, code).unwrap();
+}
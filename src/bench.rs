@@ -0,0 +1,170 @@
+use clap::Parser;
+use color_eyre::eyre::Result;
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::config;
+use crate::solutions::utils::{NotImplemented, Source};
+use crate::solve::{self, Days, Status};
+use crate::utils;
+
+/// Benchmark the solution of a day.
+///
+/// Time parsing, part 1 and part 2 separately over several runs and report min/median/max.
+#[derive(Parser, Debug)]
+pub struct Args {
+    #[command(flatten)]
+    days: Days,
+    /// Number of runs per day [default: 10, or `runs` in the configuration].
+    #[arg(short = 'n', long, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: Option<u32>,
+    /// Write the results to a CSV file.
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+/// Timings of one phase of a day over all runs.
+struct Timings {
+    day: u8,
+    phase: &'static str,
+    runs: Vec<Duration>,
+}
+
+impl Timings {
+    fn new(day: u8, phase: &'static str) -> Self {
+        Self {
+            day,
+            phase,
+            runs: vec![],
+        }
+    }
+
+    /// Time `f` and record its duration.
+    fn time<T>(&mut self, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let start = Instant::now();
+        let result = f();
+        self.runs.push(start.elapsed());
+        result
+    }
+
+    /// Record a duration measured elsewhere.
+    fn record(&mut self, duration: Duration) {
+        self.runs.push(duration);
+    }
+
+    /// Minimum, median and maximum of the recorded runs.
+    fn stats(&self) -> (Duration, Duration, Duration) {
+        let mut runs = self.runs.clone();
+        runs.sort();
+        (runs[0], runs[runs.len() / 2], runs[runs.len() - 1])
+    }
+}
+
+impl Args {
+    fn runs(&self) -> u32 {
+        self.runs.or(config::get().defaults.runs).unwrap_or(10)
+    }
+
+    /// Run parsing and both parts of a day `self.runs()` times.
+    ///
+    /// Parts that are not yet implemented are left out of the timings.
+    fn bench_day(&self, day: u8) -> Result<(Vec<Timings>, Status)> {
+        let parse = solve::parser(day)?;
+        let source = Source::File(solve::input_path(day, "puzzle"));
+
+        let mut timings = ["parse", "part1", "part2"].map(|phase| Timings::new(day, phase));
+        let mut unimplemented = vec![];
+        for _ in 0..self.runs() {
+            let puzzle = match timings[0].time(|| parse(&source)) {
+                Err(e) if e.is::<NotImplemented>() => {
+                    println!(
+                        "[!] Parsing of day {} not yet implemented",
+                        utils::fmt_day(day)
+                    );
+                    return Ok((vec![], Status::Unimplemented(vec![1, 2])));
+                }
+                puzzle => puzzle?,
+            };
+            for part in [1, 2] {
+                if unimplemented.contains(&part) {
+                    continue;
+                }
+                match puzzle.solve_part_timed(part) {
+                    Err(e) if e.is::<NotImplemented>() => unimplemented.push(part),
+                    result => timings[part as usize].record(result?.1),
+                }
+            }
+        }
+        let timings: Vec<Timings> = timings.into_iter().filter(|t| !t.runs.is_empty()).collect();
+
+        println!(
+            "================ Day {} ({} runs) ================",
+            utils::fmt_day(day),
+            self.runs()
+        );
+        println!("{:<8}{:>14}{:>14}{:>14}", "Phase", "Min", "Median", "Max");
+        for t in &timings {
+            let (min, median, max) = t.stats();
+            println!(
+                "{:<8}{:>14}{:>14}{:>14}",
+                t.phase,
+                format!("{min:.2?}"),
+                format!("{median:.2?}"),
+                format!("{max:.2?}")
+            );
+        }
+        for part in &unimplemented {
+            println!("{:<8}{:>14}", format!("part{part}"), "not yet implemented");
+        }
+
+        let status = if unimplemented.is_empty() {
+            Status::Done
+        } else {
+            Status::Unimplemented(unimplemented)
+        };
+        Ok((timings, status))
+    }
+
+    /// Write the results as CSV, with durations in nanoseconds.
+    fn save(&self, path: &str, timings: &[Timings]) -> Result<()> {
+        let mut csv = String::from("day,phase,runs,min_ns,median_ns,max_ns\n");
+        for t in timings {
+            let (min, median, max) = t.stats();
+            writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                t.day,
+                t.phase,
+                t.runs.len(),
+                min.as_nanos(),
+                median.as_nanos(),
+                max.as_nanos()
+            )?;
+        }
+        utils::write_file(path, csv)?;
+        println!("(Saved to {path})");
+        Ok(())
+    }
+}
+
+impl utils::Cmd for Args {
+    async fn run(self) -> Result<()> {
+        let mut results = vec![];
+        let outcome = match self.days.single() {
+            Some(day) => self.bench_day(day).map(|(t, _)| results.extend(t)),
+            None => solve::run_days(self.days.selected()?, "puzzle", |day| {
+                let (timings, status) = self.bench_day(day)?;
+                results.extend(timings);
+                Ok(status)
+            }),
+        };
+
+        // Keep the results of the days that succeeded even if some failed.
+        if let Some(path) = &self.output && !results.is_empty() {
+            self.save(path, &results)?;
+        }
+        outcome
+    }
+}
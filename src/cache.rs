@@ -0,0 +1,159 @@
+use color_eyre::eyre::{Result, WrapErr};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::utils;
+
+/// Directory of the HTTP cache, relative to the repository root.
+pub const CACHE_DIR: &str = ".cache/http";
+
+/// Response saved for a URL, with the validators to revalidate it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// On-disk cache of GET responses keyed by URL, or by URL and session for responses that depend
+/// on the account.
+///
+/// Each key is stored as a body file and a `.meta` file holding `etag: ...` and
+/// `last-modified: ...` lines. The cache also remembers when the last request was sent, so that
+/// requests can be spaced out across processes.
+#[derive(Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// File name of a key, with everything but ASCII alphanumerics replaced by `_`.
+    fn key(key: &str) -> String {
+        key.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Get the response saved under `key`, if any.
+    pub fn load(&self, key: &str) -> Option<Entry> {
+        let key = Self::key(key);
+        let body = fs::read_to_string(self.path(&key)).ok()?;
+        let meta = fs::read_to_string(self.path(&format!("{key}.meta"))).unwrap_or_default();
+        let mut entry = Entry {
+            body,
+            ..Default::default()
+        };
+        for line in meta.lines() {
+            match line.split_once(": ") {
+                Some(("etag", value)) => entry.etag = Some(value.into()),
+                Some(("last-modified", value)) => entry.last_modified = Some(value.into()),
+                _ => {}
+            }
+        }
+        Some(entry)
+    }
+
+    /// Save a response under `key`.
+    pub fn store(&self, key: &str, entry: &Entry) -> Result<()> {
+        fs::create_dir_all(&self.dir).wrap_err(format!(
+            "Failed to create cache dir `{}`",
+            self.dir.display()
+        ))?;
+        let key = Self::key(key);
+        let mut meta = String::new();
+        if let Some(etag) = &entry.etag {
+            meta += &format!("etag: {etag}\n");
+        }
+        if let Some(last_modified) = &entry.last_modified {
+            meta += &format!("last-modified: {last_modified}\n");
+        }
+        write(&self.path(&key), &entry.body)?;
+        write(&self.path(&format!("{key}.meta")), meta)
+    }
+
+    /// Time left to wait before the next request, so that requests are at least `interval`
+    /// apart.
+    pub fn wait_time(&self, interval: Duration) -> Duration {
+        let last = fs::read_to_string(self.path("last-request"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        match last {
+            Some(last) => (last + interval)
+                .duration_since(SystemTime::now())
+                .unwrap_or_default(),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Remember that a request is being sent now.
+    pub fn mark_request(&self) -> Result<()> {
+        fs::create_dir_all(&self.dir).wrap_err(format!(
+            "Failed to create cache dir `{}`",
+            self.dir.display()
+        ))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        write(&self.path("last-request"), now.to_string())
+    }
+}
+
+fn write(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    utils::write_file(path.display().to_string().as_str(), content)
+}
+
+/// Empty cache in a directory of its own, so that tests running at the same time don't share it.
+#[cfg(test)]
+pub fn temp_cache(name: &str) -> Cache {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let n = COUNT.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("aoc22-{name}-{}-{n}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    Cache::new(dir)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{temp_cache, Entry};
+
+    #[test]
+    fn store_and_load() {
+        let cache = temp_cache("cache-store");
+        let url = "https://adventofcode.com/2022/day/1";
+        assert_eq!(cache.load(url), None);
+
+        let entry = Entry {
+            body: "page".into(),
+            etag: Some("\"abc\"".into()),
+            last_modified: None,
+        };
+        cache.store(url, &entry).unwrap();
+        assert_eq!(cache.load(url), Some(entry));
+        assert_eq!(cache.load("https://adventofcode.com/2022/day/2"), None);
+    }
+
+    #[test]
+    fn wait_time() {
+        let cache = temp_cache("cache-wait");
+        let interval = Duration::from_secs(60);
+        assert_eq!(cache.wait_time(interval), Duration::ZERO);
+
+        cache.mark_request().unwrap();
+        assert!(cache.wait_time(interval) > Duration::from_secs(59));
+        assert_eq!(cache.wait_time(Duration::ZERO), Duration::ZERO);
+    }
+}
@@ -0,0 +1,300 @@
+use color_eyre::eyre::{ensure, Result, WrapErr};
+use reqwest::{header, RequestBuilder, Response, StatusCode};
+use std::time::Duration;
+
+use crate::cache::{Cache, Entry, CACHE_DIR};
+use crate::config;
+
+/// Site used unless `url` is configured.
+pub const SITE_URL: &str = "https://adventofcode.com";
+/// Sent unless configured otherwise, as the site asks tools to identify themselves.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (Advent of Code solutions and helper CLI)"
+);
+/// Minimum time between two requests to the site, across all subcommands and processes.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Client for adventofcode.com, authenticated with the session cookie when it is set.
+pub struct Client {
+    http: reqwest::Client,
+    /// URL of the puzzles of the year, e.g. `https://adventofcode.com/2022`.
+    base_url: String,
+    /// Fingerprint of the session, if any, telling apart the responses of different accounts.
+    session_id: Option<u64>,
+    /// Cache of GET responses, also used to space out requests by `min_interval`.
+    cache: Option<Cache>,
+    min_interval: Duration,
+}
+
+impl Client {
+    /// Client for the configured site and session, caching responses in `.cache/http`.
+    pub fn new() -> Result<Self> {
+        let config = config::get();
+        let client = Self::with_url(&config.url, config.session.clone(), &config.user_agent)?;
+        Ok(client.with_cache(Cache::new(CACHE_DIR), MIN_INTERVAL))
+    }
+
+    /// Client for the site at `site_url`, authenticated with `session` if given, without cache.
+    pub fn with_url(site_url: &str, session: Option<String>, user_agent: &str) -> Result<Self> {
+        let mut headers = header::HeaderMap::new();
+        if let Some(session) = &session {
+            headers.insert(
+                header::COOKIE,
+                format!("session={session}")
+                    .parse()
+                    .wrap_err("Failed to use session as cookie")?,
+            );
+        }
+
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .user_agent(user_agent)
+            .build()?;
+        Ok(Self {
+            http,
+            base_url: format!("{}/{}", site_url.trim_end_matches('/'), config::get().year),
+            session_id: session.as_deref().map(fingerprint),
+            cache: None,
+            min_interval: Duration::ZERO,
+        })
+    }
+
+    /// Cache responses in `cache` and send requests at least `min_interval` apart.
+    pub fn with_cache(self, cache: Cache, min_interval: Duration) -> Self {
+        Self {
+            cache: Some(cache),
+            min_interval,
+            ..self
+        }
+    }
+
+    /// Fail unless requests carry the session cookie.
+    pub fn require_session(&self) -> Result<()> {
+        ensure!(
+            self.session_id.is_some(),
+            "No session set, use the SESSION env var or `session` in aoc.toml"
+        );
+        Ok(())
+    }
+
+    /// Get the puzzle page of a day.
+    pub async fn page(&self, day: u8) -> Result<String> {
+        // The page changes once part 1 is solved, so revalidate it.
+        self.get(format!("{}/day/{day}", self.base_url), false)
+            .await
+    }
+
+    /// Get the puzzle input of a day.
+    pub async fn input(&self, day: u8) -> Result<String> {
+        self.require_session()?;
+        self.get(format!("{}/day/{day}/input", self.base_url), true)
+            .await
+    }
+
+    /// Submit an answer and get the response page.
+    pub async fn submit(&self, day: u8, part: u8, answer: &str) -> Result<String> {
+        self.require_session()?;
+        let url = format!("{}/day/{day}/answer", self.base_url);
+        let request = self
+            .http
+            .post(&url)
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)]);
+        let body = self
+            .send(request)
+            .await?
+            .error_for_status()
+            .wrap_err(format!("Failed to post to {url}"))?
+            .text()
+            .await?;
+        Ok(body)
+    }
+
+    /// Get a URL through the cache, reusing the cached response without asking the site if it
+    /// never changes, or revalidating it with its ETag/Last-Modified otherwise.
+    async fn get(&self, url: String, immutable: bool) -> Result<String> {
+        // Responses to requests with a session depend on its account, e.g. inputs.
+        let key = match self.session_id {
+            Some(id) => format!("{url} session {id:016x}"),
+            None => url.clone(),
+        };
+        let cached = self.cache.as_ref().and_then(|cache| cache.load(&key));
+        let mut request = self.http.get(&url);
+        if let Some(entry) = cached {
+            if immutable {
+                return Ok(entry.body);
+            }
+            if let Some(etag) = &entry.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+            let response = self.send(request).await?;
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(entry.body);
+            }
+            return self.save(&url, &key, response).await;
+        }
+        let response = self.send(request).await?;
+        self.save(&url, &key, response).await
+    }
+
+    /// Read the body of a successful response to a GET and cache it under `key`.
+    async fn save(&self, url: &str, key: &str, response: Response) -> Result<String> {
+        let response = response
+            .error_for_status()
+            .wrap_err(format!("Failed to get {url}"))?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &header::HeaderValue| value.to_str().ok())
+                .map(String::from)
+        };
+        let etag = header(header::ETAG);
+        let last_modified = header(header::LAST_MODIFIED);
+        let entry = Entry {
+            body: response.text().await?,
+            etag,
+            last_modified,
+        };
+        if let Some(cache) = &self.cache {
+            cache.store(key, &entry)?;
+        }
+        Ok(entry.body)
+    }
+
+    /// Send a request, first waiting for `min_interval` to pass since the last one.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        if let Some(cache) = &self.cache {
+            let wait = cache.wait_time(self.min_interval);
+            if !wait.is_zero() {
+                println!(
+                    "[-] Waiting {:.1}s before sending the next request.",
+                    wait.as_secs_f32()
+                );
+                tokio::time::sleep(wait).await;
+            }
+            cache.mark_request()?;
+        }
+        Ok(request.send().await?)
+    }
+}
+
+/// 64-bit FNV-1a hash of a session, which unlike `DefaultHasher` is stable across builds, to key
+/// the cache by session without writing the session itself to disk.
+fn fingerprint(session: &str) -> u64 {
+    session.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{Client, USER_AGENT};
+    use crate::cache::temp_cache;
+    use crate::fake_server::FakeServer;
+
+    #[tokio::test]
+    async fn page_without_session() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1", "day_1.html")]);
+        let client = Client::with_url(&server.url, None, USER_AGENT).unwrap();
+        let page = client.page(1).await.unwrap();
+        assert!(page.contains("Calorie Counting"));
+        assert_eq!(server.requests()[0].cookie, None);
+    }
+
+    #[tokio::test]
+    async fn input_requires_session() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1/input", "day_1_input.txt")]);
+        let client = Client::with_url(&server.url, None, USER_AGENT).unwrap();
+        assert!(client.input(1).await.is_err());
+        assert!(server.requests().is_empty());
+
+        let client = Client::with_url(&server.url, Some("abc".into()), USER_AGENT).unwrap();
+        assert_eq!(client.input(1).await.unwrap(), "1000\n2000\n\n3000\n");
+        assert_eq!(server.requests()[0].cookie.as_deref(), Some("session=abc"));
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let server = FakeServer::start(&[]);
+        let client = Client::with_url(&server.url, None, USER_AGENT).unwrap();
+        assert!(client.page(2).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn user_agent() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1", "day_1.html")]);
+        let client = Client::with_url(&server.url, None, "aoc22-test").unwrap();
+        client.page(1).await.unwrap();
+        assert_eq!(
+            server.requests()[0].user_agent.as_deref(),
+            Some("aoc22-test")
+        );
+    }
+
+    #[tokio::test]
+    async fn revalidate_cached_page() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1", "day_1.html")]);
+        let client = Client::with_url(&server.url, None, USER_AGENT)
+            .unwrap()
+            .with_cache(temp_cache("client-page"), Duration::ZERO);
+        let page = client.page(1).await.unwrap();
+        assert_eq!(client.page(1).await.unwrap(), page);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].if_none_match, None);
+        assert!(requests[1].if_none_match.is_some());
+    }
+
+    #[tokio::test]
+    async fn reuse_cached_input() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1/input", "day_1_input.txt")]);
+        let client = Client::with_url(&server.url, Some("abc".into()), USER_AGENT)
+            .unwrap()
+            .with_cache(temp_cache("client-input"), Duration::ZERO);
+        let input = client.input(1).await.unwrap();
+        assert_eq!(client.input(1).await.unwrap(), input);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn cache_input_per_session() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1/input", "day_1_input.txt")]);
+        let cache = temp_cache("client-sessions");
+        for session in ["abc", "def", "abc"] {
+            Client::with_url(&server.url, Some(session.into()), USER_AGENT)
+                .unwrap()
+                .with_cache(cache.clone(), Duration::ZERO)
+                .input(1)
+                .await
+                .unwrap();
+        }
+        let cookies: Vec<_> = server.requests().into_iter().map(|r| r.cookie).collect();
+        assert_eq!(
+            cookies,
+            [Some("session=abc".into()), Some("session=def".into())]
+        );
+    }
+
+    #[tokio::test]
+    async fn min_interval() {
+        let server = FakeServer::start(&[("GET", "/2022/day/1", "day_1.html")]);
+        let interval = Duration::from_millis(300);
+        let cache = temp_cache("client-interval");
+        let client = Client::with_url(&server.url, None, USER_AGENT)
+            .unwrap()
+            .with_cache(cache, interval);
+        let start = Instant::now();
+        client.page(1).await.unwrap();
+        client.page(1).await.unwrap();
+        assert!(start.elapsed() >= interval);
+    }
+}
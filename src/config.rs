@@ -0,0 +1,173 @@
+use color_eyre::eyre::{ensure, Result, WrapErr};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use std::{env, path::Path};
+
+use crate::client;
+use crate::utils;
+
+/// Configuration file read from the current directory unless `--config` says otherwise.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Solutions directory `build.rs` registered the days from, read from `aoc.toml` at build time.
+const BUILT_SOLUTIONS_DIR: &str = env!("AOC_SOLUTIONS_DIR");
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+
+/// Settings shared by all subcommands.
+///
+/// Read from `aoc.toml`, then overridden by the `SESSION`, `AOC_URL` and `AOC_USER_AGENT` env
+/// vars (also loaded from `.env`), then by command line flags.
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Session cookie of the Advent of Code account.
+    pub session: Option<String>,
+    pub year: u16,
+    /// Root of the testdata directories of each day.
+    pub testdata_dir: String,
+    /// Directory of the `yYYYY/day_XX.rs` solutions.
+    ///
+    /// `build.rs` registers the days from the one in `aoc.toml` when building, so another file
+    /// given with `--config` must not set a different one.
+    pub solutions_dir: String,
+    /// Site to download puzzles from and submit answers to.
+    pub url: String,
+    pub user_agent: String,
+    pub defaults: Defaults,
+}
+
+/// Default values of subcommand flags.
+#[derive(Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    /// Testdata stage of `solve`.
+    pub stage: Option<String>,
+    /// Whether `solve` checks the answers against the saved ones unless saving them.
+    pub check: bool,
+    /// Number of runs of `bench`.
+    pub runs: Option<u32>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            year: 2022,
+            testdata_dir: "testdata".into(),
+            solutions_dir: BUILT_SOLUTIONS_DIR.into(),
+            url: client::SITE_URL.into(),
+            user_agent: client::USER_AGENT.into(),
+            defaults: Defaults::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Read the configuration file, using the defaults if `path` is the default one and it
+    /// doesn't exist, and apply the env vars.
+    pub fn load(path: Option<&str>) -> Result<Self> {
+        dotenvy::dotenv().ok();
+        let mut config = match path {
+            Some(path) => Self::parse(&utils::read_file(path)?)
+                .wrap_err(format!("Failed to parse config file `{path}`"))?,
+            None if Path::new(CONFIG_FILE).exists() => Self::parse(&utils::read_file(CONFIG_FILE)?)
+                .wrap_err(format!("Failed to parse config file `{CONFIG_FILE}`"))?,
+            None => Self::default(),
+        };
+        config.apply_env(|name| env::var(name).ok());
+        ensure!(
+            config.solutions_dir == BUILT_SOLUTIONS_DIR,
+            "`solutions_dir` is `{}` but the solutions were built from `{BUILT_SOLUTIONS_DIR}`: \
+            set it in `{CONFIG_FILE}` and rebuild instead",
+            config.solutions_dir
+        );
+        Ok(config)
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Override settings with the env vars found by `var`.
+    fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) {
+        if let Some(session) = var("SESSION") {
+            self.session = Some(session);
+        }
+        if let Some(url) = var("AOC_URL") {
+            self.url = url;
+        }
+        if let Some(user_agent) = var("AOC_USER_AGENT") {
+            self.user_agent = user_agent;
+        }
+    }
+}
+
+/// Make `config` the configuration returned by [`get`].
+pub fn set(config: Config) {
+    CONFIG
+        .set(config)
+        .expect("Configuration should be set only once");
+}
+
+/// The configuration in use, the defaults if none was set (e.g. in tests).
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Config, Defaults};
+
+    #[test]
+    fn parse() {
+        let config = Config::parse(
+            r#"
+session = "abc"
+year = 2021
+testdata_dir = "data"
+
+[defaults]
+stage = "sample"
+check = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some("abc".into()),
+                year: 2021,
+                testdata_dir: "data".into(),
+                defaults: Defaults {
+                    stage: Some("sample".into()),
+                    check: true,
+                    runs: None,
+                },
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_setting() {
+        assert!(Config::parse("sesion = \"abc\"").is_err());
+    }
+
+    #[test]
+    fn solutions_dir_must_match_build() {
+        let path = std::env::temp_dir().join(format!("aoc22-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "solutions_dir = \"elsewhere\"").unwrap();
+        let result = Config::load(path.to_str());
+        std::fs::remove_file(&path).unwrap();
+        assert!(format!("{:#}", result.unwrap_err()).contains("`elsewhere`"));
+    }
+
+    #[test]
+    fn env_overrides_file() {
+        let mut config = Config::parse("session = \"abc\"\nurl = \"http://localhost\"").unwrap();
+        config.apply_env(|name| (name == "SESSION").then(|| "def".into()));
+        assert_eq!(config.session.as_deref(), Some("def"));
+        assert_eq!(config.url, "http://localhost");
+    }
+}
@@ -0,0 +1,140 @@
+//! Stand-in for adventofcode.com serving fixture files, so that the client and the scraping code
+//! can be tested without network access.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Directory of the pages and inputs served by the fake server.
+const FIXTURES_DIR: &str = "fixtures";
+
+/// A request received by the fake server.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub user_agent: Option<String>,
+    pub if_none_match: Option<String>,
+    pub body: String,
+}
+
+/// HTTP server on a local port answering requests from `routes`, running until the test ends.
+pub struct FakeServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl FakeServer {
+    /// Serve the fixture file of each `(method, path, fixture)` route, 404 for anything else.
+    pub fn start(routes: &[(&str, &str, &str)]) -> Self {
+        let routes: HashMap<(String, String), String> = routes
+            .iter()
+            .map(|(method, path, fixture)| {
+                let body = std::fs::read_to_string(format!("{FIXTURES_DIR}/{fixture}"))
+                    .unwrap_or_else(|e| panic!("Failed to read fixture `{fixture}`: {e}"));
+                ((method.to_string(), path.to_string()), body)
+            })
+            .collect();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let Some(request) = read_request(&stream) else {
+                    continue;
+                };
+                let body = routes.get(&(request.method.clone(), request.path.clone()));
+                let if_none_match = request.if_none_match.clone();
+                received.lock().unwrap().push(request);
+                let _ = respond(stream, body, if_none_match.as_deref());
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut splits = line.split_whitespace();
+    let method = splits.next()?.to_string();
+    let path = splits.next()?.to_string();
+
+    let mut cookie = None;
+    let mut user_agent = None;
+    let mut if_none_match = None;
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => cookie = Some(value.trim().to_string()),
+            "user-agent" => user_agent = Some(value.trim().to_string()),
+            "if-none-match" => if_none_match = Some(value.trim().to_string()),
+            "content-length" => length = value.trim().parse().ok()?,
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    Some(Request {
+        method,
+        path,
+        cookie,
+        user_agent,
+        if_none_match,
+        body: String::from_utf8(body).ok()?,
+    })
+}
+
+/// Respond with `body` and an ETag derived from it, or with 304 if the client already has it.
+fn respond(
+    mut stream: TcpStream,
+    body: Option<&String>,
+    if_none_match: Option<&str>,
+) -> io::Result<()> {
+    let Some(body) = body else {
+        let body = "Not Found";
+        return write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+    };
+
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    let etag = format!("\"{:x}\"", hasher.finish());
+    if if_none_match == Some(etag.as_str()) {
+        return write!(
+            stream,
+            "HTTP/1.1 304 Not Modified\r\nETag: {etag}\r\nConnection: close\r\n\r\n"
+        );
+    }
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nETag: {etag}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
@@ -0,0 +1,172 @@
+use color_eyre::eyre::{bail, eyre, Result};
+use std::{fmt::Write, fs, path::Path};
+
+use crate::solve;
+use crate::submit::Verdict;
+use crate::utils;
+
+/// Hint given by the server for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Hint {
+    TooHigh,
+    TooLow,
+    None,
+}
+
+impl Hint {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::TooHigh => "too-high",
+            Self::TooLow => "too-low",
+            Self::None => "wrong",
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "too-high" => Ok(Self::TooHigh),
+            "too-low" => Ok(Self::TooLow),
+            "wrong" => Ok(Self::None),
+            _ => bail!("Unknown hint `{s}`"),
+        }
+    }
+}
+
+/// A wrong answer submitted for a part.
+#[derive(Debug, PartialEq, Eq)]
+struct Guess {
+    part: u8,
+    hint: Hint,
+    answer: String,
+}
+
+/// Wrong answers submitted for a day, stored in `submissions.txt` of the testdata of the day as
+/// lines of
+/// `<part> <too-high|too-low|wrong> <answer>`.
+#[derive(Debug, Default)]
+pub struct History {
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    pub fn path(day: u8) -> String {
+        format!("{}/submissions.txt", solve::day_dir(day))
+    }
+
+    /// Load the history of a day, empty if nothing was submitted yet.
+    pub fn load(day: u8) -> Result<Self> {
+        let path = Self::path(day);
+        if !Path::new(&path).exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&utils::read_file(path.as_str())?)
+    }
+
+    fn parse(s: &str) -> Result<Self> {
+        let guesses = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                let mut splits = l.splitn(3, ' ');
+                let mut next = || splits.next().ok_or(eyre!("Failed to parse guess: {l}"));
+                Ok(Guess {
+                    part: next()?.parse()?,
+                    hint: Hint::parse(next()?)?,
+                    answer: next()?.into(),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { guesses })
+    }
+
+    /// Save the history of a day.
+    pub fn save(&self, day: u8) -> Result<()> {
+        let mut content = String::new();
+        for g in &self.guesses {
+            writeln!(content, "{} {} {}", g.part, g.hint.as_str(), g.answer)?;
+        }
+        let path = Self::path(day);
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        utils::write_file(path.as_str(), content)
+    }
+
+    /// Remember a wrong answer, ignoring verdicts that aren't about the answer itself.
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict) {
+        let hint = match verdict {
+            Verdict::TooHigh => Hint::TooHigh,
+            Verdict::TooLow => Hint::TooLow,
+            Verdict::Wrong => Hint::None,
+            _ => return,
+        };
+        self.guesses.push(Guess {
+            part,
+            hint,
+            answer: answer.into(),
+        });
+    }
+
+    /// Fail if an answer is known to be wrong, either submitted before or outside the bounds
+    /// given by earlier too high/too low hints.
+    pub fn check(&self, part: u8, answer: &str) -> Result<()> {
+        let guesses = self.guesses.iter().filter(|g| g.part == part);
+        if let Some(g) = guesses.clone().find(|g| g.answer == answer) {
+            bail!(
+                "{answer} was already submitted for part {part} ({})",
+                g.hint.as_str()
+            );
+        }
+
+        let Ok(answer) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |hint| {
+            guesses
+                .clone()
+                .filter(move |g| g.hint == hint)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Hint::TooHigh).min() && answer >= high {
+            bail!("{answer} is not lower than {high}, which is already too high");
+        }
+        if let Some(low) = bound(Hint::TooLow).max() && answer <= low {
+            bail!("{answer} is not higher than {low}, which is already too low");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::History;
+    use crate::submit::Verdict;
+
+    const HISTORY: &str =
+        "1 too-high 500\n1 too-low 100\n1 too-high 400\n1 wrong abc\n2 too-low 900\n";
+
+    #[test_case(1, "250", true ; "within bounds")]
+    #[test_case(1, "400", false ; "too high")]
+    #[test_case(1, "450", false ; "above too high")]
+    #[test_case(1, "100", false ; "too low")]
+    #[test_case(1, "abc", false ; "already wrong")]
+    #[test_case(1, "xyz", true ; "not a number")]
+    #[test_case(2, "450", false ; "other part")]
+    #[test_case(2, "1000", true ; "other part within bounds")]
+    fn check(part: u8, answer: &str, ok: bool) {
+        let history = History::parse(HISTORY).unwrap();
+        assert_eq!(history.check(part, answer).is_ok(), ok);
+    }
+
+    #[test]
+    fn record() {
+        let mut history = History::default();
+        history.record(1, "42", &Verdict::TooLow);
+        history.record(1, "50", &Verdict::Wait(30));
+        assert!(history.check(1, "50").is_ok());
+        assert!(history.check(1, "41").is_err());
+        assert!(history.check(1, "43").is_ok());
+    }
+}
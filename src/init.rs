@@ -1,16 +1,31 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use clap::Parser;
-use color_eyre::eyre::{bail, ensure, eyre, Result, WrapErr};
-use regex::Regex;
-use reqwest::header;
+use color_eyre::eyre::{bail, ensure, eyre, Result};
 use scraper::{Html, Selector};
 use std::{
-    env, fs,
+    fs,
+    io::{self, Write},
     path::{Path, PathBuf},
+    time::Duration,
 };
 
+use crate::client::Client;
+use crate::config;
+use crate::markdown;
+use crate::solve;
 use crate::utils;
 
+/// Save the puzzle description of a day as Markdown to `puzzle.md` in the testdata of the day.
+pub fn save_description(day: u8, page: &str) -> Result<()> {
+    let dir = solve::day_dir(day);
+    fs::create_dir_all(&dir)?;
+    let path = format!("{dir}/puzzle.md");
+    let markdown = markdown::from_page(page, &config::get().url)?;
+    utils::write_file(path.as_str(), markdown)?;
+    println!("[-] Puzzle description saved to {path}.");
+    Ok(())
+}
+
 /// Initialize the solution of a day.
 ///
 /// Download puzzle and sample input/output and generate solution from template.
@@ -19,26 +34,56 @@ pub struct Args {
     /// Specify the day of the puzzle.
     #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
     day: u8,
+    /// Wait for the puzzle to unlock if it is not available yet, then initialize it.
+    #[arg(short, long)]
+    wait: bool,
+}
+
+/// Time the puzzle of a day is released: midnight in US Eastern time, which is UTC-5 in December.
+fn unlock_time(year: u16, day: u8) -> DateTime<Utc> {
+    FixedOffset::west_opt(5 * 3600)
+        .unwrap()
+        .with_ymd_and_hms(year.into(), 12, day.into(), 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// Format the time left before an unlock as `[Dd ]HH:MM:SS`, rounding up to the second.
+fn fmt_countdown(left: Duration) -> String {
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let hms = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    match days {
+        0 => hms,
+        days => format!("{days}d {hms}"),
+    }
 }
 
 impl Args {
-    /// Check whether the puzzle is available yet.
-    /// New puzzle is released at UTC 05:00 each day.
-    fn validate_day(&self) -> bool {
-        let day = DateTime::<Utc>::from_utc(
-            NaiveDate::from_ymd_opt(2022, 12, self.day.into())
-                .unwrap()
-                .and_hms_opt(5, 0, 0)
-                .unwrap(),
-            Utc,
-        );
-        let now = Utc::now();
-        now >= day
+    /// Wait until `unlock` with a live countdown, then a little more so as not to request the
+    /// puzzle before it is out.
+    async fn wait_for_unlock(&self, unlock: DateTime<Utc>) -> Result<()> {
+        println!("[-] Day {} unlocks at {unlock}.", self.day);
+        while let Ok(left) = (unlock - Utc::now()).to_std() {
+            print!("\r[-] Unlocking in {}", fmt_countdown(left));
+            io::stdout().flush()?;
+            tokio::time::sleep(left.min(Duration::from_secs(1))).await;
+        }
+        println!();
+
+        let jitter = Duration::from_millis(1000 + u64::from(Utc::now().timestamp_subsec_millis()));
+        println!("[-] Unlocked, downloading in {:.1}s.", jitter.as_secs_f32());
+        tokio::time::sleep(jitter).await;
+        Ok(())
     }
 
     /// Download testdata files.
     async fn download(&self) -> Result<()> {
-        let (input, output) = self.get_sample().await?;
+        let client = Client::new()?;
+        let page = client.page(self.day).await?;
+        save_description(self.day, &page)?;
+
+        let (input, output) = self.get_sample(&page)?;
         println!("[!] Please verify that the following are correct. Otherwise, please manually copy them from the website.\n");
         println!("[Sample Input]");
         println!("{input}\n");
@@ -46,19 +91,15 @@ impl Args {
         println!("{output}\n");
         self.create_testdata_files("sample", input, Some(output))?;
 
-        let input = self.get_puzzle().await?;
+        let input = self.get_puzzle(&client).await?;
         self.create_testdata_files("puzzle", input, None)?;
 
         Ok(())
     }
 
-    /// Get the puzzle's sample input and output (for part 1).
-    async fn get_sample(&self) -> Result<(String, String)> {
-        let body = reqwest::get(format!("https://adventofcode.com/2022/day/{}", self.day))
-            .await?
-            .text()
-            .await?;
-        let doc = Html::parse_document(&body);
+    /// Get the puzzle's sample input and output (for part 1) from the puzzle page.
+    fn get_sample(&self, page: &str) -> Result<(String, String)> {
+        let doc = Html::parse_document(page);
 
         let input_sel = Selector::parse("pre > code").unwrap();
         let input = doc
@@ -82,29 +123,8 @@ impl Args {
     }
 
     /// Get the puzzle's input.
-    async fn get_puzzle(&self) -> Result<String> {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::COOKIE,
-            format!("session={}", env::var("SESSION")?)
-                .parse()
-                .wrap_err("Failed to get SESSION env var")?,
-        );
-
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .build()?;
-
-        let input = client
-            .get(format!(
-                "https://adventofcode.com/2022/day/{}/input",
-                self.day
-            ))
-            .send()
-            .await?
-            .text()
-            .await?;
-
+    async fn get_puzzle(&self, client: &Client) -> Result<String> {
+        let input = client.input(self.day).await?;
         Ok(input.trim_end().into())
     }
 
@@ -115,10 +135,10 @@ impl Args {
         input: String,
         output: Option<String>,
     ) -> Result<()> {
-        let dir = PathBuf::from(format!("testdata/day_{:0>2}/{stage}/", self.day));
-        fs::create_dir_all(dir.clone())?;
+        let dir = PathBuf::from(solve::stage_dir(self.day, stage));
+        fs::create_dir_all(&dir)?;
 
-        let path = dir.clone().join("input.txt");
+        let path = dir.join("input.txt");
         fs::write(&path, input)?;
         println!(
             "[-] {} input saved to {}.",
@@ -126,8 +146,8 @@ impl Args {
             path.display()
         );
 
-        let path = dir.clone().join("output-part1.txt");
-        fs::write(&path, output.clone().unwrap_or("0".into()))?;
+        let path = dir.join("output-part1.txt");
+        fs::write(&path, output.clone().unwrap_or(utils::PLACEHOLDER.into()))?;
         if output.is_some() {
             println!(
                 "[-] {} output (part 1) saved to {}.",
@@ -136,38 +156,41 @@ impl Args {
             );
         }
 
-        fs::write(dir.clone().join("output-part2.txt"), "0")?;
+        fs::write(dir.join("output-part2.txt"), utils::PLACEHOLDER)?;
 
         Ok(())
     }
 
+    /// Path of the solution file `yYYYY/day_XX.rs` of the day.
+    fn solution_path(&self) -> String {
+        let config = config::get();
+        format!(
+            "{}/y{}/day_{}.rs",
+            config.solutions_dir,
+            config.year,
+            utils::fmt_day(self.day)
+        )
+    }
+
     /// Generate solution from template.
     fn generate(&self) -> Result<()> {
-        let day = format!("{:0>2}", self.day);
+        let day = utils::fmt_day(self.day);
 
-        // Generate solution file solutions/day_XX.rs.
+        // Generate solution file solutions/yYYYY/day_XX.rs.
         let template = utils::read_file("./src/solutions/day_template.rs")?;
-        let path = format!("./src/solutions/day_{day}.rs");
-        utils::write_file(path.as_str(), template.replace("XX", &day))?;
+        let path = self.solution_path();
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let solution = template.replace(
+            "aoc_tests!(day = DAY)",
+            &format!("aoc_tests!(day = {})", self.day),
+        );
+        utils::write_file(path.as_str(), solution)?;
         println!("[-] Created solution file {path}.");
 
-        // Modify solutions/mod.rs to include the solution module.
-        let path = "./src/solutions/mod.rs";
-        let content = utils::read_file(path)?;
-        let mut lines: Vec<&str> = content.split("\n").collect();
-        let mod_str = format!("pub mod day_{day};");
-        lines.push(mod_str.as_str());
-        lines.sort();
-        utils::write_file(path, lines.join("\n").trim())?;
-
-        // Modify solve.rs to add an match arm for the day.
-        let path = "./src/solve.rs";
-        let mut content = utils::read_file(path)?;
-        let re = Regex::new(r"seq!\(D in 01..=\d{2} \{")?;
-        content = re
-            .replace(&content, format!("seq!(D in 01..={day} {{"))
-            .to_string();
-        utils::write_file(path, content)?;
+        // The new module is picked up by build.rs, no need to register it anywhere.
+        println!("[-] Day {day} will be registered on the next build.");
 
         Ok(())
     }
@@ -176,24 +199,31 @@ impl Args {
 impl utils::Cmd for Args {
     async fn run(self) -> Result<()> {
         // Check the puzzle is available
+        let year = config::get().year;
+        ensure!(
+            year >= 2015,
+            "There are no puzzles in {year}, the first year is 2015"
+        );
+        let unlock = unlock_time(year, self.day);
         ensure!(
-            self.validate_day(),
-            "Puzzle for day {} is not available yet",
+            self.wait || Utc::now() >= unlock,
+            "Puzzle for day {} of {year} is not available yet, it unlocks at {unlock} \
+            (use --wait to wait for it)",
             self.day
         );
 
         // Check that files to be created don't already exist
-        let day = format!("{:0>2}", self.day);
-        let paths = [
-            format!("./src/solutions/day_{day}.rs"),
-            format!("./testdata/day_{day}/"),
-        ];
+        let paths = [self.solution_path(), solve::day_dir(self.day)];
         for p in paths {
             if Path::new(&p).exists() {
                 bail!("`{p}` already exists, remove to continue")
             }
         }
 
+        if Utc::now() < unlock {
+            self.wait_for_unlock(unlock).await?;
+        }
+
         // Download test data and generate solution sketch
         self.download().await?;
         self.generate()?;
@@ -201,3 +231,48 @@ impl utils::Cmd for Args {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+    use test_case::test_case;
+
+    use super::{fmt_countdown, unlock_time, Args};
+    use crate::client::{Client, USER_AGENT};
+    use crate::fake_server::FakeServer;
+
+    #[test_case(2022, 1, "2022-12-01 05:00:00 UTC" ; "first day")]
+    #[test_case(2015, 25, "2015-12-25 05:00:00 UTC" ; "last day")]
+    fn unlock(year: u16, day: u8, expected: &str) {
+        assert_eq!(unlock_time(year, day).to_string(), expected);
+    }
+
+    #[test_case(Duration::from_millis(500), "00:00:01" ; "rounded up")]
+    #[test_case(Duration::from_secs(3723), "01:02:03" ; "hours")]
+    #[test_case(Duration::from_secs(2 * 86400 + 59), "2d 00:00:59" ; "days")]
+    fn countdown(left: Duration, expected: &str) {
+        assert_eq!(fmt_countdown(left), expected);
+    }
+
+    #[tokio::test]
+    async fn download_sample_and_puzzle() {
+        let server = FakeServer::start(&[
+            ("GET", "/2022/day/1", "day_1.html"),
+            ("GET", "/2022/day/1/input", "day_1_input.txt"),
+        ]);
+        let client = Client::with_url(&server.url, Some("abc".into()), USER_AGENT).unwrap();
+        let args = Args {
+            day: 1,
+            wait: false,
+        };
+
+        let page = client.page(1).await.unwrap();
+        let (input, output) = args.get_sample(&page).unwrap();
+        assert!(input.starts_with("1000\n2000\n3000\n\n4000"));
+        assert!(input.ends_with("10000"));
+        assert_eq!(output, "24000");
+
+        let puzzle = args.get_puzzle(&client).await.unwrap();
+        assert_eq!(puzzle, "1000\n2000\n\n3000");
+    }
+}
@@ -1,18 +1,38 @@
 #![feature(iter_array_chunks)]
 #![feature(let_chains)]
 #![feature(async_fn_in_trait)]
+#![allow(incomplete_features)]
 
 use clap::{Parser, Subcommand};
 use color_eyre::Result;
 use utils::Cmd;
 
+mod bench;
+mod cache;
+mod client;
+mod config;
+#[cfg(test)]
+mod fake_server;
+mod history;
 mod init;
+mod markdown;
+mod refresh;
 mod solutions;
 mod solve;
+mod submit;
 mod utils;
 
 #[derive(Parser)]
 pub struct Opts {
+    /// Read the configuration from this file instead of `aoc.toml`.
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+    /// Year of the puzzles, overriding the configuration.
+    #[arg(long, global = true)]
+    pub year: Option<u16>,
+    /// Root of the testdata directories, overriding the configuration.
+    #[arg(long, global = true)]
+    pub testdata_dir: Option<String>,
     #[clap(subcommand)]
     pub sub: Subcommands,
 }
@@ -23,6 +43,12 @@ pub enum Subcommands {
     Init(init::Args),
     #[clap(visible_alias = "s")]
     Solve(solve::Args),
+    #[clap(visible_alias = "b")]
+    Bench(bench::Args),
+    #[clap(visible_alias = "sub")]
+    Submit(submit::Args),
+    #[clap(visible_alias = "r")]
+    Refresh(refresh::Args),
 }
 
 #[tokio::main]
@@ -30,8 +56,20 @@ async fn main() -> Result<()> {
     color_eyre::install()?;
 
     let opts = Opts::parse();
+    let mut config = config::Config::load(opts.config.as_deref())?;
+    if let Some(year) = opts.year {
+        config.year = year;
+    }
+    if let Some(dir) = opts.testdata_dir {
+        config.testdata_dir = dir;
+    }
+    config::set(config);
+
     match opts.sub {
         Subcommands::Init(cmd) => cmd.run().await,
         Subcommands::Solve(cmd) => cmd.run().await,
+        Subcommands::Bench(cmd) => cmd.run().await,
+        Subcommands::Submit(cmd) => cmd.run().await,
+        Subcommands::Refresh(cmd) => cmd.run().await,
     }
 }
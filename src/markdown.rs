@@ -0,0 +1,114 @@
+use color_eyre::eyre::{ensure, Result};
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// Convert the puzzle description (`article.day-desc` sections) of a puzzle page to Markdown.
+///
+/// Links relative to the site are made absolute with `base_url`.
+pub fn from_page(page: &str, base_url: &str) -> Result<String> {
+    let doc = Html::parse_document(page);
+    let sel = Selector::parse("article.day-desc").unwrap();
+    let articles: Vec<String> = doc
+        .select(&sel)
+        .map(|article| blocks(article, base_url))
+        .collect();
+    ensure!(
+        !articles.is_empty(),
+        "Can't find puzzle description element"
+    );
+    Ok(articles.join("\n"))
+}
+
+/// Render the block-level children of an element, separated by blank lines.
+fn blocks(element: ElementRef, base_url: &str) -> String {
+    let mut out = String::new();
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let block = match child.value().name() {
+            "h2" => format!("## {}", inline(child, base_url).trim()),
+            "pre" => format!("```\n{}\n```", child.text().collect::<String>().trim_end()),
+            "ul" | "ol" => child
+                .children()
+                .filter_map(ElementRef::wrap)
+                .map(|li| format!("- {}", inline(li, base_url).trim()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => inline(child, base_url).trim().to_string(),
+        };
+        if !block.is_empty() {
+            out += &block;
+            out += "\n\n";
+        }
+    }
+    out
+}
+
+/// Render the inline content of an element.
+fn inline(element: ElementRef, base_url: &str) -> String {
+    let mut out = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out += &text.replace('\n', " "),
+            Node::Element(e) => {
+                let child = ElementRef::wrap(child).unwrap();
+                let content = inline(child, base_url);
+                out += &match e.name() {
+                    // Emphasis inside code can't be expressed in Markdown, keep the text only.
+                    "code" => format!("`{}`", child.text().collect::<String>()),
+                    "em" => format!("*{content}*"),
+                    "a" => match e.attr("href") {
+                        Some(href) if href.starts_with('/') => {
+                            format!("[{content}]({}{href})", base_url.trim_end_matches('/'))
+                        }
+                        Some(href) => format!("[{content}]({href})"),
+                        None => content,
+                    },
+                    "br" => "\n".into(),
+                    _ => content,
+                };
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::from_page;
+
+    #[test]
+    fn convert() {
+        let page = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>The jungle must be <em>too</em> overgrown, see <a href="/2022/about">about</a>.</p>
+<ul>
+<li>The first Elf</li>
+<li>The <code>second</code> Elf</li>
+</ul>
+<pre><code>1000
+2000
+</code></pre>
+<p>Answer: <code><em>24000</em></code>.</p>
+</article>
+<p>Not part of the description.</p>
+</main></body></html>"#;
+        let expected = "## --- Day 1: Calorie Counting ---
+
+The jungle must be *too* overgrown, see [about](https://adventofcode.com/2022/about).
+
+- The first Elf
+- The `second` Elf
+
+```
+1000
+2000
+```
+
+Answer: `24000`.
+
+";
+        assert_eq!(
+            from_page(page, "https://adventofcode.com").unwrap(),
+            expected
+        );
+    }
+}
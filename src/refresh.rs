@@ -0,0 +1,95 @@
+use clap::Parser;
+use color_eyre::eyre::{bail, eyre, Result};
+use scraper::{Html, Selector};
+
+use crate::client::Client;
+use crate::init;
+use crate::solve;
+use crate::utils;
+
+/// Refresh the testdata of a day after part 2 is unlocked.
+///
+/// Download the puzzle page again, update the puzzle description and fill in the part 2 sample
+/// output if it is still the placeholder.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Specify the day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+/// Get the sample output of part 2 from a puzzle page, `None` if part 2 is still locked.
+pub fn part2_sample_output(page: &str) -> Result<Option<String>> {
+    let doc = Html::parse_document(page);
+    let article_sel = Selector::parse("article.day-desc").unwrap();
+    let Some(article) = doc.select(&article_sel).nth(1) else {
+        return Ok(None);
+    };
+
+    let output_sel = Selector::parse("code > em").unwrap();
+    let output: String = article
+        .select(&output_sel)
+        .last()
+        .ok_or(eyre!("Can't find part 2 sample output element"))?
+        .text()
+        .collect();
+    Ok(Some(output.trim().into()))
+}
+
+impl utils::Cmd for Args {
+    async fn run(self) -> Result<()> {
+        let client = Client::new()?;
+        client.require_session()?;
+        let page = client.page(self.day).await?;
+        init::save_description(self.day, &page)?;
+
+        let Some(output) = part2_sample_output(&page)? else {
+            bail!("Part 2 of day {} is not unlocked yet", self.day);
+        };
+        println!("[Sample Output (Part 2)]");
+        println!("{output}\n");
+
+        // The sample downloaded by `init` is the first case, others are added by hand.
+        let dir = solve::stage_dir(self.day, "sample");
+        let case = solve::cases(&dir).into_iter().next().unwrap_or(dir);
+        let path = solve::output_path(&case, 2);
+        let saved = utils::read_file(path.as_str())?;
+        if saved.trim() != utils::PLACEHOLDER {
+            println!(
+                "[!] {path} already holds {}, leaving it as is.",
+                saved.trim()
+            );
+            return Ok(());
+        }
+        utils::write_file(path.as_str(), output)?;
+        println!("[-] Sample output (part 2) saved to {path}.");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::part2_sample_output;
+
+    const PART1: &str = r#"<article class="day-desc"><h2>--- Day 1 ---</h2>
+        <p>Sample:</p><pre><code>1
+2</code></pre><p>In this example, the answer is <code><em>24000</em></code>.</p></article>"#;
+    const PART2: &str = r#"<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+        <p>In the example above, the answer is <code><em>45000</em></code>.</p></article>"#;
+
+    #[test]
+    fn locked() {
+        let page = format!("<html><body><main>{PART1}</main></body></html>");
+        assert_eq!(part2_sample_output(&page).unwrap(), None);
+    }
+
+    #[test]
+    fn unlocked() {
+        let page = format!("<html><body><main>{PART1}{PART2}</main></body></html>");
+        assert_eq!(
+            part2_sample_output(&page).unwrap(),
+            Some("45000".to_string())
+        );
+    }
+}
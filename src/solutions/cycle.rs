@@ -0,0 +1,182 @@
+//! Shortcuts for simulations that end up repeating themselves, to answer for step counts too
+//! large to simulate.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States of a simulation up to the first repeated one.
+///
+/// The state after step `start + len` has the same key as the one after step `start`, so from
+/// `start` on the simulation goes around a cycle of `len` steps.
+#[derive(Debug)]
+pub struct Cycle<S> {
+    /// Number of steps before the cycle is entered.
+    pub start: usize,
+    /// Number of steps of the cycle.
+    pub len: usize,
+    /// States from the initial one to the first repeated one, both included.
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Run `step` from `initial` until a state has the key of an earlier one.
+    ///
+    /// States with the same key must lead to states with the same key, e.g. the key can leave out
+    /// an accumulated metric like a score or a distance travelled, so that the metric can be
+    /// extrapolated with [`Cycle::extrapolate`].
+    pub fn find<K: Hash + Eq>(
+        initial: S,
+        mut step: impl FnMut(&S) -> S,
+        mut key: impl FnMut(&S) -> K,
+    ) -> Self {
+        let mut seen = HashMap::new();
+        let mut states = vec![initial];
+        loop {
+            let n = states.len() - 1;
+            let state = &states[n];
+            let k = key(state);
+            if let Some(&start) = seen.get(&k) {
+                return Self {
+                    start,
+                    len: n - start,
+                    states,
+                };
+            }
+            seen.insert(k, n);
+            let next = step(state);
+            states.push(next);
+        }
+    }
+
+    /// Index in `states` of a state with the key of the one after `n` steps.
+    fn index(&self, n: usize) -> usize {
+        if n < self.start + self.len {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// State with the key of the one after `n` steps.
+    ///
+    /// This is the state itself only if everything in it is part of the key.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.index(n)]
+    }
+
+    /// Value of `metric` after `n` steps, assuming that it changes by the same amount on each
+    /// trip around the cycle.
+    pub fn extrapolate(&self, n: usize, metric: impl Fn(&S) -> i64) -> i64 {
+        let at = |i: usize| metric(&self.states[i]);
+        if n < self.start + self.len {
+            return at(n);
+        }
+        let laps = ((n - self.start) / self.len) as i64;
+        let per_lap = at(self.start + self.len) - at(self.start);
+        at(self.index(n)) + laps * per_lap
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::Cycle;
+    use crate::solutions::geom::{Dir4, Point2};
+
+    type Point = Point2<i32>;
+
+    /// Rope whose head repeats the same motions, moving the rest of the rope like in day 9.
+    #[derive(Clone, Debug)]
+    struct Rope {
+        knots: Vec<Point>,
+        /// Steps taken so far.
+        steps: usize,
+    }
+
+    /// One step per direction: the head goes around a loop drifting 2 to the right each time.
+    const MOTIONS: [Dir4; 8] = [
+        Dir4::Right,
+        Dir4::Right,
+        Dir4::Right,
+        Dir4::Up,
+        Dir4::Up,
+        Dir4::Left,
+        Dir4::Down,
+        Dir4::Down,
+    ];
+
+    impl Rope {
+        fn new(len: usize) -> Self {
+            Self {
+                knots: vec![Point::default(); len],
+                steps: 0,
+            }
+        }
+
+        fn step(&self) -> Self {
+            let mut knots = self.knots.clone();
+            knots[0] += MOTIONS[self.steps % MOTIONS.len()].vector();
+            for i in 1..knots.len() {
+                let (ahead, knot) = (knots[i - 1], knots[i]);
+                if ahead.chebyshev(knot) >= 2 {
+                    knots[i] += (ahead - knot).signum();
+                }
+            }
+            Self {
+                knots,
+                steps: self.steps + 1,
+            }
+        }
+
+        /// Shape of the rope and position in the motions, but not where the rope is.
+        fn key(&self) -> (Vec<Point>, usize) {
+            let head = self.knots[0];
+            let shape = self.knots.iter().map(|&knot| knot - head).collect();
+            (shape, self.steps % MOTIONS.len())
+        }
+
+        fn tail_x(&self) -> i64 {
+            self.knots.last().unwrap().x.into()
+        }
+    }
+
+    fn simulate(rope: &Rope, n: usize) -> Rope {
+        (0..n).fold(rope.clone(), |rope, _| rope.step())
+    }
+
+    #[test_case(2 ; "short rope")]
+    #[test_case(10 ; "long rope")]
+    fn extrapolate_rope(len: usize) {
+        let rope = Rope::new(len);
+        let cycle = Cycle::find(rope.clone(), Rope::step, Rope::key);
+        assert_eq!(cycle.len % MOTIONS.len(), 0);
+        for n in [0, 1, 7, 8, 9, 100, 123, 1000] {
+            let expected = simulate(&rope, n);
+            assert_eq!(cycle.extrapolate(n, Rope::tail_x), expected.tail_x(), "{n}");
+            assert_eq!(cycle.state_at(n).key(), expected.key(), "{n}");
+        }
+        // The tail drifts with the head, 2 for each time around the motions.
+        let n = 1_000_000_000_000;
+        let later = cycle.extrapolate(n + MOTIONS.len(), Rope::tail_x);
+        assert_eq!(later - cycle.extrapolate(n, Rope::tail_x), 2);
+    }
+
+    #[test]
+    fn cycle_start() {
+        // Doubling mod 20 gives 1, 2, 4, 8, 16, 12 and then 4 again after 6 steps.
+        let cycle = Cycle::find(1, |&x| x * 2 % 20, |&x| x);
+        assert_eq!((cycle.start, cycle.len), (2, 4));
+        assert_eq!(*cycle.state_at(5), 12);
+        assert_eq!(*cycle.state_at(6), 4);
+        assert_eq!(*cycle.state_at(1_000_000_003), 8);
+        assert_eq!(cycle.extrapolate(1_000_000_003, |&x| x), 8);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let cycle = Cycle::find(0, |&x: &i64| (x + 1).min(3), |&x| x);
+        assert_eq!((cycle.start, cycle.len), (3, 1));
+        assert_eq!(*cycle.state_at(usize::MAX), 3);
+    }
+}
@@ -0,0 +1,442 @@
+//! Points, directions and bounding boxes on integer coordinates.
+
+use color_eyre::eyre::{bail, Error, Result};
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Signed integer usable as a coordinate.
+pub trait Coord:
+    Copy
+    + Debug
+    + Default
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$t>::signum(self)
+                }
+            }
+        )*
+    };
+}
+coord!(i8, i16, i32, i64, i128, isize);
+
+/// Point or vector in the plane, with `y` pointing up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Distance to `other` moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs()
+    }
+
+    /// Distance to `other` moving along the axes and diagonally.
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs())
+    }
+
+    /// Vector of the signs of the coordinates, i.e. the single step (possibly diagonal) towards
+    /// the direction of this vector.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The 4 points sharing a side with this one.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir.vector())
+    }
+
+    /// The 8 points sharing a side or a corner with this one.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.vector())
+    }
+}
+
+/// Point or vector in space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Distance to `other` moving only along the axes.
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    /// Distance to `other` moving along the axes and diagonally.
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs()).max(d.z.abs())
+    }
+
+    /// Vector of the signs of the coordinates.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// The 6 points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        let (zero, one) = (T::default(), T::ONE);
+        [
+            Self::new(one, zero, zero),
+            Self::new(zero, one, zero),
+            Self::new(zero, zero, one),
+        ]
+        .into_iter()
+        .flat_map(move |d| [self + d, self - d])
+    }
+}
+
+/// Implement the arithmetic operators of a point type, coordinate by coordinate.
+macro_rules! point_ops {
+    ($point:ident { $($c:ident),* }) => {
+        impl<T: Coord> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($c: self.$c + other.$c),* }
+            }
+        }
+
+        impl<T: Coord> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coord> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($c: self.$c - other.$c),* }
+            }
+        }
+
+        impl<T: Coord> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        /// Scaling by a number.
+        impl<T: Coord> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, k: T) -> Self {
+                Self { $($c: self.$c * k),* }
+            }
+        }
+
+        impl<T: Coord> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($c: -self.$c),* }
+            }
+        }
+    };
+}
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+/// One of the 4 directions along the axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Unit vector of this direction.
+    pub fn vector<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::default(), T::ONE);
+        match self {
+            Self::Up => Point2::new(zero, one),
+            Self::Right => Point2::new(one, zero),
+            Self::Down => Point2::new(zero, -one),
+            Self::Left => Point2::new(-one, zero),
+        }
+    }
+
+    /// Direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Direction a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = Error;
+
+    /// Parse `U`, `R`, `D`, `L` or the compass points `N`, `E`, `S`, `W`.
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "U" | "N" => Self::Up,
+            "R" | "E" => Self::Right,
+            "D" | "S" => Self::Down,
+            "L" | "W" => Self::Left,
+            _ => bail!("Failed to parse `{s}` as a direction"),
+        })
+    }
+}
+
+/// One of the 4 directions along the axes or the 4 diagonals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// Vector of a step in this direction, north being up.
+    pub fn vector<T: Coord>(self) -> Point2<T> {
+        let (zero, one) = (T::default(), T::ONE);
+        let (x, y) = match self {
+            Self::N => (zero, one),
+            Self::NE => (one, one),
+            Self::E => (one, zero),
+            Self::SE => (one, -one),
+            Self::S => (zero, -one),
+            Self::SW => (-one, -one),
+            Self::W => (-one, zero),
+            Self::NW => (-one, one),
+        };
+        Point2::new(x, y)
+    }
+
+    /// Direction an eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Direction an eighth of a turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Self::N,
+            Dir4::Right => Self::E,
+            Dir4::Down => Self::S,
+            Dir4::Left => Self::W,
+        }
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = Error;
+
+    /// Parse compass points like `N` or `SW`, or `U`, `R`, `D`, `L`.
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "NE" => Self::NE,
+            "SE" => Self::SE,
+            "SW" => Self::SW,
+            "NW" => Self::NW,
+            _ => s.parse::<Dir4>()?.into(),
+        })
+    }
+}
+
+/// Smallest axis-aligned rectangle containing some points, bounds included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> BoundingBox<T> {
+    /// Box of the given points, none if there are no points.
+    pub fn of(points: impl IntoIterator<Item = Point2<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self {
+            min: first,
+            max: first,
+        };
+        points.for_each(|p| bounds.extend(p));
+        Some(bounds)
+    }
+
+    /// Grow the box to contain `p`.
+    pub fn extend(&mut self, p: Point2<T>) {
+        self.min = Point2::new(self.min.x.min(p.x), self.min.y.min(p.y));
+        self.max = Point2::new(self.max.x.max(p.x), self.max.y.max(p.y));
+    }
+
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    /// Number of columns of points in the box.
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::ONE
+    }
+
+    /// Number of rows of points in the box.
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::ONE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{BoundingBox, Dir4, Dir8, Point2, Point3};
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point2::new(1, -2), Point2::new(-3, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        let (a, b) = (Point3::new(1, 2, 3), Point3::new(0, 4, -2));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (8, 5));
+    }
+
+    #[test]
+    fn ops() {
+        let mut p = Point2::new(1, 2);
+        p += Point2::new(3, -4);
+        assert_eq!(p, Point2::new(4, -2));
+        assert_eq!(p * 2 - Point2::new(1, 1), Point2::new(7, -5));
+        assert_eq!(-p, Point2::new(-4, 2));
+        assert_eq!(p.signum(), Point2::new(1, -1));
+        assert_eq!(Point2::new(0, 3).signum(), Point2::new(0, 1));
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point2::new(0, 0);
+        assert_eq!(
+            p.neighbours4().collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (0, -1), (-1, 0)].map(|(x, y)| Point2::new(x, y))
+        );
+        assert!(p.neighbours8().all(|q| q.chebyshev(p) == 1));
+        assert_eq!(p.neighbours8().count(), 8);
+        let p = Point3::new(0, 0, 0);
+        assert!(p.neighbours6().all(|q| q.manhattan(p) == 1));
+        assert_eq!(p.neighbours6().count(), 6);
+    }
+
+    #[test_case("R", Dir4::Right ; "right")]
+    #[test_case("U", Dir4::Up ; "up")]
+    #[test_case("S", Dir4::Down ; "south")]
+    #[test_case("W", Dir4::Left ; "west")]
+    fn parse_dir4(s: &str, expected: Dir4) {
+        assert_eq!(s.parse::<Dir4>().unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_dir8() {
+        assert_eq!("NE".parse::<Dir8>().unwrap(), Dir8::NE);
+        assert_eq!("L".parse::<Dir8>().unwrap(), Dir8::W);
+        assert!("X".parse::<Dir8>().is_err());
+        assert!("".parse::<Dir4>().is_err());
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::SE.opposite(), Dir8::NW);
+        for dir in Dir4::ALL {
+            assert_eq!(Dir8::from(dir).vector::<i32>(), dir.vector(), "{dir:?}");
+            assert_eq!(dir.turn_right().vector::<i32>(), {
+                let v = dir.vector::<i32>();
+                Point2::new(v.y, -v.x)
+            });
+        }
+    }
+
+    #[test]
+    fn bounding_box() {
+        assert_eq!(BoundingBox::<i32>::of([]), None);
+        let points = [(2, 3), (-1, 5), (0, 0)].map(|(x, y)| Point2::new(x, y));
+        let bounds = BoundingBox::of(points).unwrap();
+        assert_eq!(bounds.min, Point2::new(-1, 0));
+        assert_eq!(bounds.max, Point2::new(2, 5));
+        assert_eq!((bounds.width(), bounds.height()), (4, 6));
+        assert!(points.into_iter().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(Point2::new(3, 0)));
+    }
+}
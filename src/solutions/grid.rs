@@ -0,0 +1,307 @@
+//! Rectangular grids of cells, as found in many puzzle inputs.
+
+use color_eyre::eyre::{ensure, Result};
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::solutions::geom::{Dir4, Dir8, Point2};
+use crate::solutions::parse;
+use crate::solutions::utils::Input;
+
+/// Position of a cell as `(row, column)`, from the top left corner.
+pub type Pos = (usize, usize);
+
+/// Change of `(row, column)` of a step along `vector`, up being towards the first row.
+fn delta(vector: Point2<isize>) -> (isize, isize) {
+    (-vector.y, vector.x)
+}
+
+/// Grid of `height` rows of `width` cells, stored row after row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Grid of the given rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        ensure!(
+            rows.iter().all(|row| row.len() == width),
+            "Rows of a grid should have the same length"
+        );
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Grid of the characters of the input lines, each turned into a cell by `cell`.
+    pub fn parse(input: Input, cell: impl Fn(char) -> Option<T> + Copy) -> Result<Self> {
+        Self::from_rows(parse::all(input, parse::grid(cell))?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    /// Positions of all cells, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Position next to `pos` in direction `dir`, if it is in the grid.
+    pub fn step(&self, pos: Pos, dir: Dir4) -> Option<Pos> {
+        self.offset(pos, delta(dir.vector()))
+    }
+
+    fn offset(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Positions of the up to 4 cells sharing a side with `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Positions of the up to 8 cells sharing a side or a corner with `pos`.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(pos, delta(dir.vector())))
+    }
+
+    /// Positions from next to `pos` to the edge of the grid in direction `dir`.
+    pub fn ray(&self, pos: Pos, dir: Dir4) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, dir), move |&pos| self.step(pos, dir))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "Column {col} is out of the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Grid with the same shape and each cell turned into another by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(row, col)| (col, row))
+    }
+
+    /// Grid turned a quarter clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(height, self.width, |(row, col)| (height - 1 - col, row))
+    }
+
+    /// Grid turned a quarter counterclockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.height, width, |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Grid of `width` by `height` whose cell at each position is the one of this grid at
+    /// `source(pos)`.
+    fn rearrange(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let positions = (0..height).flat_map(|row| (0..width).map(move |col| (row, col)));
+        Self {
+            width,
+            height,
+            cells: positions.map(|pos| self[source(pos)].clone()).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is out of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is out of the grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    /// One line per row, without separators between cells.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{Grid, Pos};
+    use crate::solutions::geom::Dir4;
+    use crate::solutions::utils::Input;
+
+    fn digits(text: &str) -> Grid<u8> {
+        Grid::parse(Input::new(text), |c| c.to_digit(10).map(|d| d as u8)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert!(Grid::parse(Input::new("12\n3"), |c| c.to_digit(10)).is_err());
+        assert!(Grid::parse(Input::new("12\n3x"), |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn from_rows() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap(),
+            digits("12\n34")
+        );
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test_case((0, 0), &[(0, 1), (1, 0)] ; "corner")]
+    #[test_case((1, 1), &[(0, 1), (1, 2), (2, 1), (1, 0)] ; "middle")]
+    #[test_case((2, 1), &[(1, 1), (2, 2), (2, 0)] ; "edge")]
+    fn neighbours4(pos: Pos, expected: &[Pos]) {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours4(pos).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn neighbours8() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (1, 0)]
+        );
+    }
+
+    #[test_case(Dir4::Up, &[] ; "up")]
+    #[test_case(Dir4::Right, &[(0, 2), (0, 3)] ; "right")]
+    #[test_case(Dir4::Down, &[(1, 1), (2, 1)] ; "down")]
+    #[test_case(Dir4::Left, &[(0, 0)] ; "left")]
+    fn ray(dir: Dir4, expected: &[Pos]) {
+        let grid = Grid::new(4, 3, 0);
+        assert_eq!(grid.ray((0, 1), dir).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn rearrange() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose(), digits("14\n25\n36"));
+        assert_eq!(grid.rotate_cw(), digits("41\n52\n63"));
+        assert_eq!(grid.rotate_ccw(), digits("36\n25\n14"));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn display() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            grid.map(|&d| d % 2 == 0)
+                .map(|&b| if b { '#' } else { '.' })
+                .to_string(),
+            ".#.\n#.#"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Inclusive ranges of integers and sets of them, e.g. for the cells covered by sensors.
+
+use std::fmt::Debug;
+use std::ops::{Add, RangeInclusive, Sub};
+
+/// Integer, signed or not, usable as a bound of an interval.
+pub trait Integer: Copy + Debug + Default + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ONE: Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Integers from `start` to `end`, both included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// Interval from `start` to `end`, unless it ends before it starts.
+    pub fn new(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Number of integers in the interval.
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x <= self.end
+    }
+
+    /// Whether every integer of `other` is in this interval.
+    pub fn covers(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether some integer is in both intervals.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Integers in both intervals, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// Whether the intervals overlap or one starts right after the other ends, so that their
+    /// union is an interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.start <= other.end + T::ONE && other.start <= self.end + T::ONE
+    }
+}
+
+/// Fails with the range itself if it is empty, e.g. `5..=3`.
+impl<T: Integer> TryFrom<RangeInclusive<T>> for Interval<T> {
+    type Error = RangeInclusive<T>;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self, Self::Error> {
+        Self::new(*range.start(), *range.end()).ok_or(range)
+    }
+}
+
+/// Set of integers stored as sorted intervals, with overlapping and adjacent ones merged.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Disjoint intervals of the set, in increasing order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::default(), |len, interval| len + interval.len())
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        // Index of the first interval ending at or after `x`.
+        let i = self.intervals.partition_point(|interval| interval.end < x);
+        self.intervals
+            .get(i)
+            .map_or(false, |interval| interval.contains(x))
+    }
+
+    /// Add the integers of `interval`, merging it with the intervals it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self
+            .intervals
+            .partition_point(|other| other.end + T::ONE < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end + T::ONE);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, other| Interval {
+                start: merged.start.min(other.start),
+                end: merged.end.max(other.end),
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Remove the integers of `interval`.
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        let mut kept = vec![];
+        for other in &self.intervals[first..last] {
+            if other.start < interval.start {
+                kept.push(Interval {
+                    start: other.start,
+                    end: interval.start - T::ONE,
+                });
+            }
+            if interval.end < other.end {
+                kept.push(Interval {
+                    start: interval.end + T::ONE,
+                    end: other.end,
+                });
+            }
+        }
+        self.intervals.splice(first..last, kept);
+    }
+
+    /// Integers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.intervals.iter().for_each(|&i| union.insert(i));
+        union
+    }
+
+    /// Integers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        other.intervals.iter().for_each(|&i| difference.remove(i));
+        difference
+    }
+
+    /// Number of integers of `within` that are in the set.
+    pub fn coverage(&self, within: Interval<T>) -> T {
+        self.intervals
+            .iter()
+            .filter_map(|interval| interval.intersection(&within))
+            .fold(T::default(), |len, interval| len + interval.len())
+    }
+
+    /// Intervals of the integers of `within` that are not in the set.
+    pub fn gaps(&self, within: Interval<T>) -> Vec<Interval<T>> {
+        let mut gaps = Self::from_iter([within]);
+        self.intervals.iter().for_each(|&i| gaps.remove(i));
+        gaps.intervals
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        intervals.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::RangeInclusive;
+    use test_case::test_case;
+
+    use super::{Integer, Interval, IntervalSet};
+
+    fn iv<T: Integer>(start: T, end: T) -> Interval<T> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|&(a, b)| iv(a, b)).collect()
+    }
+
+    #[test]
+    fn interval() {
+        let a = iv(2, 6);
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(2) && a.contains(6) && !a.contains(7));
+        assert!(a.covers(&iv(3, 6)));
+        assert!(!a.covers(&iv(1, 3)));
+        assert_eq!(Interval::try_from(-1..=1), Ok(iv(-1, 1)));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Interval::new(5, 3), None);
+        let range = RangeInclusive::new(5, 3);
+        assert_eq!(Interval::try_from(range.clone()), Err(range));
+    }
+
+    #[test]
+    fn unsigned() {
+        let mut s: IntervalSet<u64> = [iv(0, 3), iv(8, 9)].into_iter().collect();
+        s.remove(iv(0, 1));
+        s.insert(iv(4, 7));
+        assert_eq!(s.intervals(), [iv(2, 9)]);
+        assert_eq!(s.len(), 8);
+        let s = IntervalSet::from_iter([iv(0usize, 0), iv(2, 3)]);
+        assert_eq!(s.gaps(iv(0, 5)), [iv(1, 1), iv(4, 5)]);
+    }
+
+    #[test_case((2, 4), (6, 8), None ; "disjoint")]
+    #[test_case((2, 6), (4, 8), Some((4, 6)) ; "overlapping")]
+    #[test_case((2, 8), (3, 7), Some((3, 7)) ; "covering")]
+    #[test_case((5, 7), (7, 9), Some((7, 7)) ; "single")]
+    fn intersection(a: (i32, i32), b: (i32, i32), expected: Option<(i32, i32)>) {
+        let (a, b) = (iv(a.0, a.1), iv(b.0, b.1));
+        let expected = expected.map(|(start, end)| iv(start, end));
+        assert_eq!(a.intersection(&b), expected);
+        assert_eq!(b.intersection(&a), expected);
+        assert_eq!(a.overlaps(&b), expected.is_some());
+    }
+
+    #[test]
+    fn insert_merges() {
+        assert_eq!(set(&[(1, 3), (7, 9), (4, 5)]), set(&[(1, 5), (7, 9)]));
+        assert_eq!(set(&[(1, 3), (7, 9), (2, 8)]), set(&[(1, 9)]));
+        assert_eq!(
+            set(&[(10, 12), (1, 2), (5, 6)]).intervals(),
+            [(1, 2), (5, 6), (10, 12)].map(|(a, b)| iv(a, b))
+        );
+        assert_eq!(set(&[(-2, 0), (1, 1)]).intervals(), [iv(-2, 1)]);
+    }
+
+    #[test]
+    fn len_and_contains() {
+        let s = set(&[(1, 3), (2, 5), (8, 8)]);
+        assert_eq!(s.len(), 6);
+        assert!(s.contains(5) && s.contains(8));
+        assert!(!s.contains(6) && !s.contains(0) && !s.contains(9));
+        assert!(IntervalSet::<i32>::new().is_empty());
+    }
+
+    #[test]
+    fn remove() {
+        let mut s = set(&[(1, 10), (20, 30)]);
+        s.remove(iv(5, 22));
+        assert_eq!(s, set(&[(1, 4), (23, 30)]));
+        s.remove(iv(1, 4));
+        assert_eq!(s, set(&[(23, 30)]));
+        s.remove(iv(25, 25));
+        assert_eq!(s, set(&[(23, 24), (26, 30)]));
+    }
+
+    #[test]
+    fn set_operations() {
+        let (a, b) = (set(&[(1, 5), (10, 15)]), set(&[(4, 11), (20, 20)]));
+        assert_eq!(a.union(&b), set(&[(1, 15), (20, 20)]));
+        assert_eq!(a.difference(&b), set(&[(1, 3), (12, 15)]));
+        assert_eq!(b.difference(&a), set(&[(6, 9), (20, 20)]));
+    }
+
+    #[test]
+    fn coverage_and_gaps() {
+        let s = set(&[(-2, 2), (4, 6), (12, 14)]);
+        let within = iv(0, 20);
+        assert_eq!(s.coverage(within), 3 + 3 + 3);
+        assert_eq!(
+            s.gaps(within),
+            [(3, 3), (7, 11), (15, 20)].map(|(a, b)| iv(a, b))
+        );
+        assert_eq!(s.gaps(iv(4, 6)), []);
+    }
+}
@@ -1,10 +1,19 @@
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
-pub mod utils;
\ No newline at end of file
+pub mod utils;
+
+// Toolkit shared by the days: items that no day solved so far needs are kept for the next ones.
+#[allow(dead_code)]
+pub mod cycle;
+#[allow(dead_code)]
+pub mod geom;
+#[allow(dead_code)]
+pub mod grid;
+#[allow(dead_code)]
+pub mod interval;
+#[allow(dead_code)]
+pub mod parse;
+#[allow(dead_code)]
+pub mod search;
+
+// The `yYYYY::day_XX` modules and `DAYS` are generated by build.rs from the `yYYYY/day_XX.rs`
+// files in this directory.
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
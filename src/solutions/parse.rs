@@ -0,0 +1,226 @@
+//! nom combinators for the input shapes that keep coming back, so that a day can declare its
+//! grammar in a few lines and run it with [`all`].
+
+use color_eyre::eyre::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{
+        char, digit1, line_ending, multispace0, none_of, one_of, space0, space1,
+    },
+    combinator::{all_consuming, cut, eof, map_opt, map_res, not, opt, recognize, verify},
+    error::{context, VerboseError},
+    multi::{count, many0, many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated},
+    Finish,
+};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::solutions::utils::{Input, ParseError};
+
+/// Result of the parsers run by [`all`], whose errors keep the [`context`] labels of what was
+/// expected.
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Run `parser` on the whole input, allowing trailing whitespace, and locate its failure.
+pub fn all<'a, O>(
+    input: Input<'a>,
+    parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    all_consuming(terminated(parser, multispace0))(input.text())
+        .finish()
+        .map(|(_, output)| output)
+        .map_err(|e| input.nom_error(e))
+}
+
+/// Unsigned integer, e.g. `42`.
+pub fn uint<T: FromStr>(i: &str) -> IResult<T> {
+    context("a number", map_res(digit1, str::parse))(i)
+}
+
+/// Integer with an optional sign, e.g. `-42` or `+42`.
+pub fn int<T: FromStr>(i: &str) -> IResult<T> {
+    let int = map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse);
+    context("a number", int)(i)
+}
+
+/// Integers separated by commas and/or spaces, e.g. `1, -2 3`.
+pub fn ints<T: FromStr>(i: &str) -> IResult<Vec<T>> {
+    let sep = alt((recognize(delimited(space0, char(','), space0)), space1));
+    separated_list1(sep, int)(i)
+}
+
+/// Inclusive range of unsigned integers written `a-b`, with `a <= b`.
+pub fn range<T: FromStr + PartialOrd>(i: &str) -> IResult<RangeInclusive<T>> {
+    let bounds = verify(separated_pair(uint, char('-'), uint), |(start, end)| {
+        start <= end
+    });
+    let (i, (start, end)) = context("a range like `2-4`", bounds)(i)?;
+    Ok((i, start..=end))
+}
+
+/// Run of characters up to whitespace or punctuation, e.g. a name or an identifier.
+pub fn word(i: &str) -> IResult<&str> {
+    context(
+        "a word",
+        take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+    )(i)
+}
+
+/// `key: value` pair.
+pub fn key_value<'a, K, V>(
+    key: impl FnMut(&'a str) -> IResult<'a, K>,
+    value: impl FnMut(&'a str) -> IResult<'a, V>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// Records separated by `sep` with optional spaces around it, e.g. `a, b, c` for `','`.
+pub fn separated<'a, O>(
+    sep: char,
+    item: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(delimited(space0, char(sep), space0), item)
+}
+
+/// Records separated by spaces, e.g. `a b  c`.
+pub fn spaced<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(space1, item)
+}
+
+/// One `item` per line, up to a blank line or the end of the input.
+pub fn lines<'a, O>(
+    item: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    let blank = preceded(space0, alt((line_ending, eof)));
+    committed(terminated(line_ending, not(blank)), item)
+}
+
+/// Blocks separated by blank lines, e.g. `blocks(lines(uint))` for groups of numbers.
+pub fn blocks<'a, O>(
+    block: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    let sep = pair(line_ending, many1(preceded(space0, line_ending)));
+    committed(terminated(sep, not(preceded(multispace0, eof))), block)
+}
+
+/// Items separated by `sep`, where an item must follow each `sep`: once `sep` matched, failures
+/// point into the item that should follow rather than at the `sep` nom would backtrack to.
+fn committed<'a, S, O>(
+    mut sep: impl FnMut(&'a str) -> IResult<'a, S>,
+    mut item: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    move |i| {
+        let (mut i, first) = item(i)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = sep(i) {
+            let (rest, next) = cut(&mut item)(rest)?;
+            items.push(next);
+            i = rest;
+        }
+        Ok((i, items))
+    }
+}
+
+/// Rectangular grid of characters, each turned into a cell by `cell`, e.g.
+/// `grid(|c| c.to_digit(10))`, up to a blank line or the end of the input.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T> + Copy,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<Vec<T>>> {
+    move |i| {
+        let cell = || context("a grid cell", map_opt(none_of("\r\n"), cell));
+        let (i, first) = many1(cell())(i)?;
+        // Once a new row starts it must be as wide as the first one, so that failures point at
+        // the offending cell rather than at the start of the grid.
+        let (i, rest) = many0(preceded(
+            terminated(line_ending, not(alt((line_ending, eof)))),
+            cut(count(cell(), first.len())),
+        ))(i)?;
+        Ok((i, std::iter::once(first).chain(rest).collect()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{character::complete::alpha1, sequence::separated_pair};
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("42", 42 ; "unsigned")]
+    #[test_case("-42", -42 ; "negative")]
+    #[test_case("+42", 42 ; "positive")]
+    fn parse_int(i: &str, expected: i32) {
+        assert_eq!(int::<i32>(i), Ok(("", expected)));
+    }
+
+    #[test]
+    fn parse_uint() {
+        assert_eq!(uint::<u8>("12 3"), Ok((" 3", 12)));
+        assert!(uint::<u8>("-1").is_err());
+        assert!(uint::<u8>("256").is_err());
+    }
+
+    #[test_case("1,-2,3" ; "commas")]
+    #[test_case("1, -2 ,3" ; "commas and spaces")]
+    #[test_case("1 -2  3" ; "spaces")]
+    fn parse_ints(i: &str) {
+        assert_eq!(ints::<i64>(i), Ok(("", vec![1, -2, 3])));
+    }
+
+    #[test]
+    fn parse_range() {
+        assert_eq!(range::<u32>("2-4,6-8"), Ok((",6-8", 2..=4)));
+        assert_eq!(range::<u32>("3-3"), Ok(("", 3..=3)));
+        assert!(range::<u32>("4-2").is_err());
+    }
+
+    #[test]
+    fn parse_key_value() {
+        let mut monkey = key_value(word, ints::<u32>);
+        assert_eq!(monkey("items: 79, 98"), Ok(("", ("items", vec![79, 98]))));
+        assert!(monkey("items 79").is_err());
+    }
+
+    #[test]
+    fn parse_records() {
+        assert_eq!(
+            separated(';', spaced(word))("a b; c"),
+            Ok(("", vec![vec!["a", "b"], vec!["c"]]))
+        );
+    }
+
+    #[test]
+    fn parse_blocks() {
+        let i = "1\n2\n\n3\r\n \r\n\n4\n";
+        assert_eq!(
+            blocks(lines(uint::<u32>))(i),
+            Ok(("\n", vec![vec![1, 2], vec![3], vec![4]]))
+        );
+        let error = all(Input::new("1\n2\n\n3\nx\n"), blocks(lines(uint::<u32>))).unwrap_err();
+        assert_eq!((error.line, error.columns), (5, 1..2));
+    }
+
+    #[test]
+    fn parse_grid() {
+        let mut digits = grid(|c| c.to_digit(10));
+        assert_eq!(digits("12\n34"), Ok(("", vec![vec![1, 2], vec![3, 4]])));
+        assert!(digits("12\n3").is_err());
+        assert!(digits("12\n3x\n").is_err());
+        assert_eq!(digits("1\n2\n\nx"), Ok(("\n\nx", vec![vec![1], vec![2]])));
+    }
+
+    #[test]
+    fn parse_all() {
+        let parser = || lines(separated_pair(alpha1, space1, uint::<u32>));
+        let input = Input::new("R 4\nU 2\n\n");
+        assert_eq!(all(input, parser()).unwrap(), [("R", 4), ("U", 2)]);
+
+        let error = all(Input::new("R 4\nU x\n"), parser()).unwrap_err();
+        assert_eq!((error.line, error.columns), (2, 3..4));
+        assert_eq!(error.expected, "a number");
+    }
+}
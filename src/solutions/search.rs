@@ -0,0 +1,361 @@
+//! Graph searches over states given by a neighbour function, e.g. positions in a grid.
+//!
+//! Every search starts from one or more states, stops at the first state for which `is_goal`
+//! holds (pass `|_| false` to visit everything reachable) and returns what it found as a
+//! [`Found`], from which distances and paths can be read.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// States reached by a search, with their distance from the closest start and the state they
+/// were reached from.
+#[derive(Debug)]
+pub struct Found<S, C> {
+    /// First goal reached, if any.
+    pub goal: Option<S>,
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+}
+
+impl<S: Clone + Hash + Eq, C: Copy> Found<S, C> {
+    fn new() -> Self {
+        Self {
+            goal: None,
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    /// Distance of `state`, if it was reached.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// Distance of the goal, if one was reached.
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    /// States from a start to `state`, both included, if it was reached.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// States from a start to the goal, if one was reached.
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.path(self.goal.as_ref()?)
+    }
+
+    /// All reached states with their distance.
+    pub fn costs(&self) -> &HashMap<S, C> {
+        &self.costs
+    }
+}
+
+/// Breadth-first search, for graphs where every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Found<S, usize>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut found = Found::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !found.costs.contains_key(&start) {
+            found.costs.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            found.goal = Some(state);
+            break;
+        }
+        let cost = found.costs[&state] + 1;
+        for next in neighbours(&state) {
+            if !found.costs.contains_key(&next) {
+                found.costs.insert(next.clone(), cost);
+                found.parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    found
+}
+
+/// Depth-first search, for when any path will do. The cost of a state is the length of the path
+/// it was reached by, which is not necessarily the shortest.
+pub fn dfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Found<S, usize>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    let mut found = Found::new();
+    let mut stack: Vec<_> = starts.into_iter().map(|start| (start, None)).collect();
+    // Visit the starts in the given order.
+    stack.reverse();
+    while let Some((state, parent)) = stack.pop() {
+        if found.costs.contains_key(&state) {
+            continue;
+        }
+        let cost = match parent {
+            Some(parent) => {
+                let cost = found.costs[&parent] + 1;
+                found.parents.insert(state.clone(), parent);
+                cost
+            }
+            None => 0,
+        };
+        found.costs.insert(state.clone(), cost);
+        if is_goal(&state) {
+            found.goal = Some(state);
+            break;
+        }
+        for next in neighbours(&state) {
+            if !found.costs.contains_key(&next) {
+                stack.push((next, Some(state.clone())));
+            }
+        }
+    }
+    found
+}
+
+/// Dijkstra's algorithm, for graphs where `neighbours` gives the cost of each step.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Found<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search: Dijkstra's algorithm guided towards the goal by `heuristic`, which must never
+/// overestimate the cost left to reach it.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Found<S, C>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Default + Ord + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut found = Found::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        found.costs.insert(start.clone(), C::default());
+        queue.push(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            state: start,
+        });
+    }
+    while let Some(Queued { cost, state, .. }) = queue.pop() {
+        // Skip states already reached at a lower cost since they were queued.
+        if cost > found.costs[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            found.goal = Some(state);
+            break;
+        }
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if found.costs.get(&next).map_or(true, |&c| next_cost < c) {
+                found.costs.insert(next.clone(), next_cost);
+                found.parents.insert(next.clone(), state.clone());
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// State in the queue of [`astar`], popped lowest priority first.
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, dfs, dijkstra};
+    use crate::solutions::grid::{Grid, Pos};
+    use crate::solutions::utils::Input;
+
+    /// Risk levels of the sample of 2021 day 15, whose lowest total risk is 40.
+    const RISKS: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581
+";
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#.E#
+.##...#.
+";
+
+    fn digits(text: &str) -> Grid<u32> {
+        Grid::parse(Input::new(text), |c| c.to_digit(10)).unwrap()
+    }
+
+    fn maze() -> Grid<char> {
+        Grid::parse(Input::new(MAZE), Some).unwrap()
+    }
+
+    /// Open cells next to `pos`.
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours4(pos).filter(|&p| grid[p] != '#')
+    }
+
+    /// Sum of the risks of the cells entered along `path`.
+    fn risk(grid: &Grid<u32>, path: &[Pos]) -> u32 {
+        path[1..].iter().map(|&p| grid[p]).sum()
+    }
+
+    fn is_step(a: Pos, b: Pos) -> bool {
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1) == 1
+    }
+
+    #[test]
+    fn bfs_shortest_path() {
+        let grid = maze();
+        let found = bfs([(0, 0)], |&p| open(&grid, p), |&p| grid[p] == 'E');
+        assert_eq!(found.goal, Some((2, 6)));
+        assert_eq!(found.goal_cost(), Some(10));
+        let path = found.goal_path().unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[0], (0, 0));
+        assert!(path.windows(2).all(|w| is_step(w[0], w[1])));
+        assert!(path.iter().all(|&p| grid[p] != '#'));
+    }
+
+    #[test]
+    fn bfs_multiple_starts() {
+        let grid = maze();
+        let found = bfs([(0, 7), (3, 0)], |&p| open(&grid, p), |&p| grid[p] == 'E');
+        assert_eq!(found.goal_cost(), Some(9));
+        assert_eq!(found.goal_path().unwrap()[0], (3, 0));
+    }
+
+    #[test]
+    fn bfs_everything() {
+        let grid = maze();
+        let found = bfs([(0, 0)], |&p| open(&grid, p), |_| false);
+        assert_eq!(found.goal, None);
+        assert_eq!(found.cost(&(3, 7)), None);
+        assert_eq!(found.cost(&(0, 7)), Some(11));
+        assert_eq!(found.costs().len(), 20);
+    }
+
+    #[test]
+    fn dijkstra_lowest_risk() {
+        let grid = digits(RISKS);
+        let goal = (grid.height() - 1, grid.width() - 1);
+        let found = dijkstra(
+            [(0, 0)],
+            |&p| grid.neighbours4(p).map(|q| (q, grid[q])),
+            |&p| p == goal,
+        );
+        assert_eq!(found.goal_cost(), Some(40));
+        assert_eq!(risk(&grid, &found.goal_path().unwrap()), 40);
+    }
+
+    #[test]
+    fn astar_lowest_risk() {
+        let grid = digits(RISKS);
+        let goal = (grid.height() - 1, grid.width() - 1);
+        let mut expanded = 0;
+        let found = astar(
+            [(0, 0)],
+            |&p| {
+                expanded += 1;
+                grid.neighbours4(p)
+                    .map(|q| (q, grid[q]))
+                    .collect::<Vec<_>>()
+            },
+            |&(row, col)| (goal.0 - row + goal.1 - col) as u32,
+            |&p| p == goal,
+        );
+        assert_eq!(found.goal_cost(), Some(40));
+        assert_eq!(risk(&grid, &found.goal_path().unwrap()), 40);
+        assert!(expanded < grid.width() * grid.height());
+    }
+
+    #[test]
+    fn dfs_any_path() {
+        let grid = maze();
+        let found = dfs([(0, 0)], |&p| open(&grid, p), |&p| grid[p] == 'E');
+        let path = found.goal_path().unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(2, 6)));
+        assert_eq!(found.goal_cost(), Some(path.len() - 1));
+        assert!(path.windows(2).all(|w| is_step(w[0], w[1])));
+    }
+
+    #[test]
+    fn unreachable() {
+        let grid = maze();
+        let found = bfs([(0, 0)], |&p| open(&grid, p), |&p| p == (3, 7));
+        assert_eq!(found.goal, None);
+        assert_eq!(found.goal_path(), None);
+        assert_eq!(found.path(&(3, 7)), None);
+    }
+}
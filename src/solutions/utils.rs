@@ -1,62 +1,456 @@
-use color_eyre::eyre::{Result, WrapErr};
-use std::fmt::Display;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use color_eyre::eyre::{bail, Report, Result, WrapErr};
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::ops::Range;
 use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
-use crate::utils;
+/// Whole puzzle input, borrowed by parsers so that they don't need to copy it.
+#[derive(Clone, Copy, Debug)]
+pub struct Input<'a> {
+    /// Name of the input (e.g. its path) in errors.
+    name: &'a str,
+    /// Whole input, to locate errors in.
+    full: &'a str,
+    /// Part of the input this is a view of, e.g. a paragraph.
+    text: &'a str,
+}
+
+impl<'a> Input<'a> {
+    /// Unnamed input, e.g. in tests.
+    #[cfg(test)]
+    pub fn new(text: &'a str) -> Self {
+        Self::named("<input>", text)
+    }
+
+    pub fn named(name: &'a str, text: &'a str) -> Self {
+        Self {
+            name,
+            full: text,
+            text,
+        }
+    }
+
+    /// The whole input.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The whole input as bytes.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.text.as_bytes()
+    }
+
+    /// Lines of the input, without their line endings.
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+
+    /// Groups of lines separated by blank lines, without the line ending of their last line.
+    pub fn paragraphs(&self) -> impl Iterator<Item = Input<'a>> {
+        let input = *self;
+        let mut rest = self.text;
+        std::iter::from_fn(move || {
+            while let Some(line) = rest.split_inclusive('\n').next() && line.trim().is_empty() {
+                rest = &rest[line.len()..];
+            }
+            if rest.is_empty() {
+                return None;
+            }
+            let end = rest
+                .split_inclusive('\n')
+                .scan(0, |start, line| {
+                    let line_start = *start;
+                    *start += line.len();
+                    Some((line_start, line))
+                })
+                .find(|(_, line)| line.trim().is_empty())
+                .map_or(rest.len(), |(start, _)| start);
+            let (paragraph, tail) = rest.split_at(end);
+            rest = tail;
+            Some(Input {
+                text: paragraph.trim_end_matches(['\r', '\n']),
+                ..input
+            })
+        })
+    }
+
+    /// Parse each line with [`FromStr`], locating failures with what was `expected`.
+    pub fn parse_lines<T>(&self, expected: &str) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        Report: From<T::Err>,
+    {
+        self.lines()
+            .map(|l| {
+                l.parse()
+                    .map_err(|e| Report::from(e).wrap_err(self.error(l, expected)))
+            })
+            .collect()
+    }
+
+    /// Error at `span`, which must be a slice of the input, where `expected` was expected.
+    pub fn error(&self, span: &str, expected: impl Display) -> ParseError {
+        let full = self.full;
+        // Spans that are not part of the input are reported at its end.
+        let start = (span.as_ptr() as usize)
+            .checked_sub(full.as_ptr() as usize)
+            .filter(|&start| start + span.len() <= full.len())
+            .unwrap_or(full.len());
+        let line_start = full[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = full[start..].find('\n').map_or(full.len(), |i| start + i);
+        let text = full[line_start..line_end].trim_end_matches('\r');
+
+        let column = full[line_start..start].chars().count() + 1;
+        let end = (start + span.len()).min(line_start + text.len());
+        let width = full.get(start..end).map_or(0, |s| s.chars().count()).max(1);
+        ParseError {
+            name: self.name.into(),
+            line: full[..start].matches('\n').count() + 1,
+            columns: column..column + width,
+            text: text.into(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Error for a failure of a nom parser, pointing at the word where it got the furthest and
+    /// expecting what the innermost [`context`](nom::error::context) there describes.
+    pub fn nom_error(&self, e: VerboseError<&str>) -> ParseError {
+        // The deepest failure is the one with the least input left.
+        let rest = e
+            .errors
+            .iter()
+            .map(|(rest, _)| *rest)
+            .min_by_key(|rest| rest.len())
+            .unwrap_or_default();
+        let kinds = || {
+            e.errors
+                .iter()
+                .filter(|(at, _)| at.len() == rest.len())
+                .map(|(_, kind)| kind)
+        };
+        // Contexts are listed from the innermost one, so the last one describes the most.
+        let expected = kinds()
+            .filter_map(|kind| match kind {
+                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                _ => None,
+            })
+            .last()
+            .or_else(|| {
+                kinds().find_map(|kind| match kind {
+                    VerboseErrorKind::Char(c) => Some(format!("`{c}`")),
+                    VerboseErrorKind::Nom(ErrorKind::Eof) => Some("the end of the input".into()),
+                    _ => None,
+                })
+            })
+            .unwrap_or_else(|| "valid input".into());
+        let word = rest.split(char::is_whitespace).next().unwrap_or_default();
+        self.error(word, expected)
+    }
+}
+
+/// Failure to parse an input, located by line and columns.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Name of the input, e.g. its path.
+    pub name: String,
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Columns of the offending text, starting from 1.
+    pub columns: Range<usize>,
+    /// The offending line.
+    pub text: String,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    /// Show what was expected and where, with a caret under the offending text.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            name,
+            line,
+            columns,
+            text,
+            expected,
+        } = self;
+        let gutter = " ".repeat(line.to_string().len());
+        writeln!(f, "expected {expected} at {name}:{line}:{}", columns.start)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {text}")?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(columns.start - 1),
+            "^".repeat(columns.len())
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
 
 pub trait FromFile: Sized {
-    fn parse(lines: Vec<String>) -> Result<Self>;
+    fn parse(input: Input) -> Result<Self>;
     fn from_file<P>(path: P) -> Result<Self>
     where
         P: AsRef<Path> + Display + Copy,
     {
-        let lines = BufReader::new(
-            File::open(path).wrap_err(format!("Failed to open input file `{path}`"))?,
-        )
-        .lines()
-        .enumerate()
-        .map(|(i, l)| l.wrap_err(format!("Failed to read line {i} from `{path}`")))
-        .collect::<Result<Vec<String>>>()?;
-        Self::parse(lines)
+        let text =
+            fs::read_to_string(path).wrap_err(format!("Failed to read input file `{path}`"))?;
+        Self::parse(Input::named(&path.to_string(), &text))
+    }
+    /// Parse the input read from `reader`, `name` is used to refer to the input in errors.
+    fn from_reader<R: Read>(mut reader: R, name: impl Display) -> Result<Self> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .wrap_err(format!("Failed to read input from `{name}`"))?;
+        Self::parse(Input::named(&name.to_string(), &text))
+    }
+}
+
+/// Where to read the puzzle input from.
+#[derive(Clone, Debug)]
+pub enum Source {
+    File(String),
+    Stdin,
+}
+
+impl From<&str> for Source {
+    /// `-` stands for stdin, anything else is a file path.
+    fn from(s: &str) -> Self {
+        match s {
+            "-" => Self::Stdin,
+            path => Self::File(path.into()),
+        }
     }
 }
 
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{path}"),
+            Self::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Error for a puzzle parser or solution that is not written yet, reported as such instead of
+/// as a failure.
+#[derive(Debug)]
+pub struct NotImplemented;
+
+impl Display for NotImplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not yet implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
 pub trait Solution: Clone {
     type Output: Display;
 
     fn solve1(self) -> Result<Self::Output>;
     fn solve2(self) -> Result<Self::Output>;
-    fn solve(self, day: u8, save: bool) -> Result<()> {
-        let day = utils::fmt_day(day);
-        println!("================ Day {day} ================");
-
-        print!("[Part 1] ");
-        let ans1 = self
-            .clone()
-            .solve1()
-            .wrap_err("Failed to run solution for part 1")?;
-        println!("Answer: {ans1}");
-
-        if save {
-            let path = format!("./testdata/day_{day}/puzzle/output-part1.txt");
-            utils::write_file(path.as_str(), ans1.to_string())?;
-            println!("(Saved to {path})");
+}
+
+/// Object-safe view of a parsed puzzle, so that the solution of a day can be picked at runtime.
+pub trait Solve {
+    /// Run the solution of a part on a copy of the puzzle.
+    fn solve_part(&self, part: u8) -> Result<String> {
+        self.solve_part_timed(part).map(|(answer, _)| answer)
+    }
+    /// Same as [`Solve::solve_part`], also returning how long `solve1`/`solve2` alone took,
+    /// excluding copying the puzzle and formatting the answer.
+    fn solve_part_timed(&self, part: u8) -> Result<(String, Duration)>;
+}
+
+impl<T: Solution> Solve for T {
+    fn solve_part_timed(&self, part: u8) -> Result<(String, Duration)> {
+        let puzzle = self.clone();
+        let start = Instant::now();
+        let answer = match part {
+            1 => puzzle.solve1(),
+            2 => puzzle.solve2(),
+            p => bail!("Part {p} does not exist"),
+        };
+        let elapsed = start.elapsed();
+        let answer = answer.wrap_err(format!("Failed to run solution for part {part}"))?;
+        Ok((answer.to_string(), elapsed))
+    }
+}
+
+/// Parse an input into the puzzle of a day.
+pub type Parse = fn(&Source) -> Result<Box<dyn Solve>>;
+
+/// A day with a solution implemented.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Parse,
+}
+
+/// Type-erased [`FromFile`] of a puzzle type.
+pub fn parse<P>(source: &Source) -> Result<Box<dyn Solve>>
+where
+    P: FromFile + Solution + 'static,
+{
+    let puzzle = match source {
+        Source::File(path) => P::from_file(path.as_str())?,
+        Source::Stdin => P::from_reader(io::stdin().lock(), source)?,
+    };
+    Ok(Box::new(puzzle))
+}
+
+/// Run the solution of a part on every case of a stage of a day and compare the answers with
+/// the expected outputs.
+///
+/// A stage without input and an expected output that is missing or still the placeholder are
+/// reported as ignored instead of failing, since puzzle inputs are not checked in.
+///
+/// The year is taken from `module`, the path of the `yYYYY::day_XX` module of the day.
+#[cfg(test)]
+pub fn test_stage<P: FromFile + Solution>(module: &str, day: u8, stage: &str, part: u8) {
+    let year = module
+        .split("::")
+        .find_map(|m| m.strip_prefix('y')?.parse().ok())
+        .unwrap_or_else(|| panic!("Can't find the year of `{module}`"));
+    let dir = format!("{}/{stage}", crate::solve::year_day_dir(year, day));
+    let cases = crate::solve::cases(&dir);
+    if cases.is_empty() {
+        println!("[Ignored] No input found in `{dir}`");
+        return;
+    }
+    for case in cases {
+        let expected_path = crate::solve::output_path(&case, part);
+        let Some(expected) = crate::solve::read_saved(&expected_path).unwrap() else {
+            println!("[Ignored] No expected output in `{expected_path}`");
+            continue;
+        };
+        let puzzle = P::from_file(format!("{case}/input.txt").as_str()).unwrap();
+        let answer = match part {
+            1 => puzzle.solve1(),
+            2 => puzzle.solve2(),
+            _ => unreachable!(),
         }
+        .unwrap();
+        println!("[Compared] `{expected_path}`");
+        assert_eq!(
+            answer.to_string(),
+            expected,
+            "Answer differs from `{expected_path}`"
+        );
+    }
+}
 
-        print!("\n[Part 2] ");
-        let ans2 = self
-            .solve2()
-            .wrap_err("Failed to run solution for part 2")?;
-        println!("Answer: {ans2}");
+/// Generate the tests of a day, running both parts on its `sample` and `puzzle` testdata of the
+/// year of the module.
+///
+/// ```ignore
+/// aoc_tests!(day = 7);
+/// ```
+macro_rules! aoc_tests {
+    (day = $day:literal) => {
+        #[cfg(test)]
+        mod test {
+            use test_case::test_case;
 
-        if save {
-            let path = format!("./testdata/day_{day}/puzzle/output-part2.txt");
-            utils::write_file(path.as_str(), ans2.to_string())?;
-            println!("(Saved to {path})");
+            use super::Puzzle;
+            use crate::solutions::utils::test_stage;
+
+            #[test_case("sample", 1 ; "sample part1")]
+            #[test_case("puzzle", 1 ; "puzzle part1")]
+            #[test_case("sample", 2 ; "sample part2")]
+            #[test_case("puzzle", 2 ; "puzzle part2")]
+            fn test(stage: &str, part: u8) {
+                test_stage::<Puzzle>(module_path!(), $day, stage, part);
+            }
         }
+    };
+}
+pub(crate) use aoc_tests;
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use nom::{character::complete::char, sequence::separated_pair};
+    use std::ops::Range;
+
+    use super::{Input, ParseError};
+    use crate::solutions::parse::{self, range, uint};
+
+    #[test_case("1\n2\n\n3\n", &["1\n2", "3"] ; "simple")]
+    #[test_case("\n\n1\n\n\n\n2\n3", &["1", "2\n3"] ; "extra blank lines")]
+    #[test_case("1\r\n2\r\n\r\n3\r\n", &["1\r\n2", "3"] ; "crlf")]
+    #[test_case("1\n  \n2", &["1", "2"] ; "whitespace line")]
+    #[test_case("", &[] ; "empty")]
+    fn paragraphs(text: &str, expected: &[&str]) {
+        let paragraphs: Vec<&str> = Input::new(text).paragraphs().map(|p| p.text()).collect();
+        assert_eq!(paragraphs, expected);
+    }
+
+    #[test]
+    fn paragraph_lines() {
+        let input = Input::new("a\r\nb\r\n\r\nc\r\n");
+        let lines: Vec<Vec<&str>> = input.paragraphs().map(|p| p.lines().collect()).collect();
+        assert_eq!(lines, [vec!["a", "b"], vec!["c"]]);
+    }
+    #[test]
+    fn error_location() {
+        let text = "1 2\n\nab cd\n";
+        let input = Input::named("day_xx", text);
+        let paragraph = input.paragraphs().nth(1).unwrap();
+        let line = paragraph.lines().next().unwrap();
+        let error = paragraph.error(&line[3..], "a number");
+        assert_eq!(
+            error,
+            ParseError {
+                name: "day_xx".into(),
+                line: 3,
+                columns: 4..6,
+                text: "ab cd".into(),
+                expected: "a number".into(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected a number at day_xx:3:4\n  |\n3 | ab cd\n  |    ^^"
+        );
+    }
+
+    #[test]
+    fn error_at_end() {
+        let input = Input::new("ab\ncd");
+        let error = input.error("not in the input", "more");
+        assert_eq!((error.line, error.columns), (2, 3..4));
+    }
+
+    #[test_case("4 2-x", 5..6, "a number" ; "deepest failure")]
+    #[test_case("4 x-2", 3..6, "a range like `2-4`" ; "outermost context")]
+    #[test_case("4:2-3", 2..6, "` `" ; "character")]
+    fn nom_error(text: &str, columns: Range<usize>, expected: &str) {
+        let parser = separated_pair(uint::<u32>, char(' '), range::<u32>);
+        let error = parse::all(Input::new(text), parser).unwrap_err();
+        assert_eq!(
+            (error.columns, error.expected.as_str()),
+            (columns, expected)
+        );
+    }
 
-        Ok(())
+    #[test]
+    fn parse_lines() {
+        assert_eq!(
+            Input::new("1\n2\n").parse_lines::<u8>("a number").unwrap(),
+            [1, 2]
+        );
+        let report = Input::new("1\nx2\n")
+            .parse_lines::<u8>("a number")
+            .unwrap_err();
+        let error = report.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.columns.clone()), (2, 1..3));
     }
 }
@@ -0,0 +1,44 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+pub type Puzzle = Vec<u32>;
+
+impl FromFile for Puzzle {
+    fn parse(input: Input) -> Result<Self> {
+        input
+            .paragraphs()
+            .map(|elf| {
+                elf.lines()
+                    .map(|l| {
+                        l.parse::<u32>()
+                            .wrap_err_with(|| elf.error(l, "a number of calories"))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = u32;
+
+    /// Solution for part 1.
+    fn solve1(self) -> Result<Self::Output> {
+        self.iter()
+            .max()
+            .ok_or(eyre!("Failed to apply `max`"))
+            .copied()
+    }
+
+    /// Solution for part 2.
+    fn solve2(mut self) -> Result<Self::Output> {
+        self.sort();
+        self.reverse();
+        let sum = self[..3].iter().sum();
+
+        Ok(sum)
+    }
+}
+
+aoc_tests!(day = 1);
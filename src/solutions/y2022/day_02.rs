@@ -1,7 +1,7 @@
 use color_eyre::eyre::{eyre, Error, Result};
 use std::str::FromStr;
 
-use crate::solutions::utils::{FromFile, Solution};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
 
 #[derive(Clone, Copy)]
 #[repr(usize)]
@@ -63,12 +63,9 @@ impl FromStr for Round {
 pub type Puzzle = Vec<Round>;
 
 impl FromFile for Puzzle {
-    /// Parse lines read from input file into Puzzle.
-    fn parse(lines: Vec<String>) -> Result<Self> {
-        lines
-            .iter()
-            .map(|l| Round::from_str(l))
-            .collect::<Result<_>>()
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        input.parse_lines("a round like `A Y`")
     }
 }
 
@@ -96,35 +93,4 @@ impl Solution for Puzzle {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use color_eyre::eyre::WrapErr;
-    use std::fs;
-    use test_case::test_case;
-
-    use super::Puzzle;
-    use crate::solutions::utils::{FromFile, Solution};
-
-    #[test_case("sample", 1 ; "sample part1")]
-    #[test_case("puzzle", 1 ; "puzzle part1")]
-    #[test_case("sample", 2 ; "sample part2")]
-    #[test_case("puzzle", 2 ; "puzzle part2")]
-    fn test(stage: &str, part: u8) {
-        let puzzle =
-            Puzzle::from_file(format!("testdata/day_02/{stage}/input.txt").as_str()).unwrap();
-        let answer = match part {
-            1 => puzzle.solve1(),
-            2 => puzzle.solve2(),
-            _ => unreachable!(),
-        }
-        .unwrap();
-
-        let expected_path = format!("testdata/day_02/{stage}/output-part{part}.txt");
-        let expected = fs::read_to_string(&expected_path)
-            .wrap_err(format!(
-                "Failed to read expected output from {expected_path}"
-            ))
-            .unwrap();
-        assert_eq!(answer.to_string(), expected.trim());
-    }
-}
+aoc_tests!(day = 2);
@@ -0,0 +1,59 @@
+use color_eyre::eyre::Result;
+use nom::{
+    character::complete::char,
+    combinator::{map, map_res},
+    sequence::separated_pair,
+};
+
+use crate::solutions::interval::Interval;
+use crate::solutions::parse::{all, lines, range, IResult};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+/// Sections assigned to each elf of a pair.
+#[derive(Clone)]
+pub struct Pair(Interval<i32>, Interval<i32>);
+
+impl Pair {
+    fn fully_overlap(&self) -> bool {
+        self.0.covers(&self.1) || self.1.covers(&self.0)
+    }
+
+    fn overlap(&self) -> bool {
+        self.0.overlaps(&self.1)
+    }
+
+    fn parse(i: &str) -> IResult<Self> {
+        let sections = || map_res(range, Interval::try_from);
+        map(
+            separated_pair(sections(), char(','), sections()),
+            |(a, b)| Self(a, b),
+        )(i)
+    }
+}
+
+pub type Puzzle = Vec<Pair>;
+
+impl FromFile for Puzzle {
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        Ok(all(input, lines(Pair::parse))?)
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = u32;
+
+    /// Solution for part 1.
+    fn solve1(self) -> Result<Self::Output> {
+        let sum: u32 = self.iter().map(|p| p.fully_overlap() as u32).sum();
+        Ok(sum)
+    }
+
+    /// Solution for part 2.
+    fn solve2(self) -> Result<Self::Output> {
+        let sum: u32 = self.iter().map(|p| p.overlap() as u32).sum();
+        Ok(sum)
+    }
+}
+
+aoc_tests!(day = 4);
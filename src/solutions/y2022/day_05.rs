@@ -0,0 +1,138 @@
+use color_eyre::eyre::{eyre, Result};
+use nom::{
+    bytes::complete::tag,
+    combinator::{all_consuming, map_opt},
+    sequence::tuple,
+    Finish,
+};
+
+use crate::solutions::parse::{uint, IResult};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+#[derive(Clone, Debug)]
+struct Procedure {
+    number: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Procedure {
+    /// Parse `move 1 from 2 to 1`, with stacks numbered from 1.
+    fn parse(i: &str) -> IResult<Self> {
+        map_opt(
+            tuple((
+                tag("move "),
+                uint,
+                tag(" from "),
+                uint::<usize>,
+                tag(" to "),
+                uint::<usize>,
+            )),
+            |(_, number, _, from, _, to)| {
+                Some(Self {
+                    number,
+                    from: from.checked_sub(1)?,
+                    to: to.checked_sub(1)?,
+                })
+            },
+        )(i)
+    }
+}
+
+#[derive(Clone)]
+pub struct Puzzle {
+    stacks: Vec<Vec<char>>,
+    procedures: Vec<Procedure>,
+}
+
+impl FromFile for Puzzle {
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        let mut lines = input.lines().enumerate();
+
+        // parse Stacks
+        let mut stacks = vec![];
+        while let Some((i, line)) = lines.next() {
+            let s = line;
+
+            // break and consume empty line when reaching number line
+            if s.trim().starts_with('1') {
+                lines.next();
+                break;
+            }
+
+            // init stacks
+            if i == 0 {
+                stacks = vec![vec![]; (s.len() + 3) / 4];
+            }
+
+            // parse crates
+            s.chars()
+                .enumerate()
+                .filter(|(i, _)| i % 4 == 1)
+                .for_each(|(i, c)| {
+                    if c != ' ' {
+                        stacks[i / 4].push(c)
+                    }
+                });
+        }
+        for stack in &mut stacks {
+            (*stack).reverse();
+        }
+
+        // parse Procedures
+        let procedures = lines
+            .map(|(_, l)| {
+                all_consuming(Procedure::parse)(l)
+                    .finish()
+                    .map(|(_, procedure)| procedure)
+                    .map_err(|_| input.error(l, "a procedure like `move 1 from 2 to 1`"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        // println!("{:?}", procedures);
+
+        Ok(Self {
+            stacks,
+            procedures,
+        })
+    }
+}
+
+impl Puzzle {
+    fn rearrange(&mut self, all_at_once: bool) {
+        for p in &self.procedures {
+            let from = &mut self.stacks[p.from];
+            let mut crates = from.split_off(from.len() - p.number);
+            if !all_at_once {
+                crates.reverse();
+            }
+            self.stacks[p.to].append(&mut crates);
+        }
+    }
+
+    fn top(&self) -> Result<String> {
+        self.stacks
+            .iter()
+            .map(|s| s.last().ok_or(eyre!("Empty stack")))
+            .collect::<Result<_>>()
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = String;
+
+    /// Solution for part 1.
+    fn solve1(mut self) -> Result<Self::Output> {
+        self.rearrange(false);
+        self.top()
+    }
+
+    /// Solution for part 2.
+    fn solve2(mut self) -> Result<Self::Output> {
+        self.rearrange(true);
+        self.top()
+    }
+}
+
+aoc_tests!(day = 5);
@@ -0,0 +1,52 @@
+use color_eyre::eyre::{ensure, Result};
+
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+#[derive(Clone)]
+pub struct Puzzle(Vec<u8>);
+
+impl FromFile for Puzzle {
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        let signal: Vec<u8> = input
+            .bytes()
+            .iter()
+            .copied()
+            .take_while(|b| !b.is_ascii_whitespace())
+            .collect();
+        ensure!(!signal.is_empty(), "Failed to parse file");
+        Ok(Self(signal))
+    }
+}
+
+impl Puzzle {
+    fn decode(self, n: usize) -> Result<usize> {
+        let mut last_seen = [None; u8::MAX as usize];
+        let mut start_pos = 0;
+        for (i, c) in self.0.into_iter().enumerate() {
+            if let Some(p) = last_seen[c as usize] && p >= start_pos {
+            start_pos = p + 1;
+        } else if i - start_pos + 1 == n {
+            break;
+        }
+            last_seen[c as usize] = Some(i);
+        }
+        Ok(start_pos + n)
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = usize;
+
+    /// Solution for part 1.
+    fn solve1(self) -> Result<Self::Output> {
+        self.decode(4)
+    }
+
+    /// Solution for part 2.
+    fn solve2(self) -> Result<Self::Output> {
+        self.decode(14)
+    }
+}
+
+aoc_tests!(day = 6);
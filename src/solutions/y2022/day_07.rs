@@ -1,22 +1,23 @@
 use camino::Utf8PathBuf;
-use color_eyre::eyre::{eyre, Result, WrapErr};
+use color_eyre::eyre::{eyre, Result};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_while1},
     character::complete::u64,
-    combinator::{all_consuming, map},
+    combinator::map,
+    error::context,
     sequence::{preceded, separated_pair},
-    Finish, IResult,
 };
 use std::cell::RefCell;
 use std::collections::{hash_map, HashMap};
 use std::rc::Rc;
 
-use crate::solutions::utils::{FromFile, Solution};
+use crate::solutions::parse::{all, lines, IResult};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
 
 const PATHCHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ./";
 
-fn parse_path(i: &str) -> IResult<&str, Utf8PathBuf> {
+fn parse_path(i: &str) -> IResult<Utf8PathBuf> {
     map(take_while1(|c: char| PATHCHARS.contains(c)), Into::into)(i)
 }
 
@@ -24,7 +25,7 @@ fn parse_path(i: &str) -> IResult<&str, Utf8PathBuf> {
 struct Ls;
 
 impl Ls {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         map(tag("ls"), |_| Self)(i)
     }
 }
@@ -33,7 +34,7 @@ impl Ls {
 struct Cd(Utf8PathBuf);
 
 impl Cd {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         map(preceded(tag("cd "), parse_path), Cd)(i)
     }
 }
@@ -45,7 +46,7 @@ enum Cmd {
 }
 
 impl Cmd {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         let (i, _) = tag("$ ")(i)?;
         alt((map(Ls::parse, Cmd::Ls), map(Cd::parse, Cmd::Cd)))(i)
     }
@@ -57,7 +58,7 @@ struct Dir {
 }
 
 impl Dir {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         map(preceded(tag("dir "), parse_path), |path| Dir { path })(i)
     }
 }
@@ -69,7 +70,7 @@ struct File {
 }
 
 impl File {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         map(separated_pair(u64, tag(" "), parse_path), |(size, path)| {
             File { size, path }
         })(i)
@@ -83,7 +84,7 @@ enum Entry {
 }
 
 impl Entry {
-    fn parse(i: &str) -> IResult<&str, Self> {
+    fn parse(i: &str) -> IResult<Self> {
         alt((map(Dir::parse, Entry::Dir), map(File::parse, Entry::File)))(i)
     }
 
@@ -102,8 +103,9 @@ enum Line {
 }
 
 impl Line {
-    fn parse(i: &str) -> IResult<&str, Self> {
-        alt((map(Cmd::parse, Line::Cmd), map(Entry::parse, Line::Entry)))(i)
+    fn parse(i: &str) -> IResult<Self> {
+        let line = alt((map(Cmd::parse, Line::Cmd), map(Entry::parse, Line::Entry)));
+        context("a command or a directory entry", line)(i)
     }
 }
 
@@ -136,19 +138,9 @@ pub struct Puzzle {
 }
 
 impl FromFile for Puzzle {
-    /// Parse lines read from input file into Puzzle.
-    fn parse(lines: Vec<String>) -> Result<Self> {
-        // HACK: Deliberately leak memory to make lines to have 'static lifetime.
-        let lines = Box::leak(Box::new(lines));
-        let term = lines
-            .iter()
-            .map(|l| {
-                Ok(all_consuming(Line::parse)(l)
-                    .finish()
-                    .wrap_err("Failed to parse input")?
-                    .1)
-            })
-            .collect::<Result<Vec<_>>>()?;
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        let term = all(input, lines(Line::parse))?;
 
         let root = Rc::new(RefCell::new(Node {
             entry: Entry::Dir(Dir { path: "/".into() }),
@@ -224,7 +216,7 @@ impl Puzzle {
             ans += size;
         }
 
-        return (size, ans);
+        (size, ans)
     }
 
     /// Returns tuple of (size, ans)
@@ -256,7 +248,7 @@ impl Puzzle {
             ans = size;
         }
 
-        return (size, ans);
+        (size, ans)
     }
 }
 
@@ -282,35 +274,4 @@ impl Solution for Puzzle {
     }
 }
 
-#[cfg(test)]
-mod test {
-    use color_eyre::eyre::WrapErr;
-    use std::fs;
-    use test_case::test_case;
-
-    use super::Puzzle;
-    use crate::solutions::utils::{FromFile, Solution};
-
-    #[test_case("sample", 1 ; "sample part1")]
-    #[test_case("puzzle", 1 ; "puzzle part1")]
-    #[test_case("sample", 2 ; "sample part2")]
-    #[test_case("puzzle", 2 ; "puzzle part2")]
-    fn test(stage: &str, part: u8) {
-        let puzzle =
-            Puzzle::from_file(format!("testdata/day_07/{stage}/input.txt").as_str()).unwrap();
-        let answer = match part {
-            1 => puzzle.solve1(),
-            2 => puzzle.solve2(),
-            _ => unreachable!(),
-        }
-        .unwrap();
-
-        let expected_path = format!("testdata/day_07/{stage}/output-part{part}.txt");
-        let expected = fs::read_to_string(&expected_path)
-            .wrap_err(format!(
-                "Failed to read expected output from {expected_path}"
-            ))
-            .unwrap();
-        assert_eq!(answer.to_string(), expected.trim());
-    }
-}
+aoc_tests!(day = 7);
@@ -0,0 +1,71 @@
+use color_eyre::eyre::Result;
+
+use crate::solutions::geom::Dir4;
+use crate::solutions::grid::{Grid, Pos};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+#[derive(Clone)]
+pub struct Puzzle {
+    trees: Grid<u8>,
+}
+
+impl Puzzle {
+    /// Whether the tree at `pos` can be seen from outside the grid.
+    fn visible(&self, pos: Pos) -> bool {
+        let height = self.trees[pos];
+        Dir4::ALL
+            .into_iter()
+            .any(|dir| self.trees.ray(pos, dir).all(|p| self.trees[p] < height))
+    }
+
+    /// Number of trees seen from the tree at `pos` looking in direction `dir`.
+    fn viewing_distance(&self, pos: Pos, dir: Dir4) -> usize {
+        let height = self.trees[pos];
+        let mut n = 0;
+        for p in self.trees.ray(pos, dir) {
+            n += 1;
+            if self.trees[p] >= height {
+                break;
+            }
+        }
+        n
+    }
+}
+
+impl FromFile for Puzzle {
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        let trees = Grid::parse(input, |c| c.to_digit(10).map(|height| height as u8))?;
+        Ok(Puzzle { trees })
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = usize;
+
+    /// Solution for part 1.
+    fn solve1(self) -> Result<Self::Output> {
+        Ok(self
+            .trees
+            .positions()
+            .filter(|&pos| self.visible(pos))
+            .count())
+    }
+
+    /// Solution for part 2.
+    fn solve2(self) -> Result<Self::Output> {
+        Ok(self
+            .trees
+            .positions()
+            .map(|pos| {
+                Dir4::ALL
+                    .into_iter()
+                    .map(|dir| self.viewing_distance(pos, dir))
+                    .product()
+            })
+            .max()
+            .unwrap_or_default())
+    }
+}
+
+aoc_tests!(day = 8);
@@ -0,0 +1,94 @@
+use color_eyre::eyre::{Context, Result};
+use std::collections::HashSet;
+
+use crate::solutions::geom::{Dir4, Point2};
+use crate::solutions::utils::{aoc_tests, FromFile, Input, Solution};
+
+type Point = Point2<i32>;
+
+#[derive(Clone)]
+struct Motion {
+    dir: Dir4,
+    num: usize,
+}
+
+#[derive(Clone, Default)]
+pub struct Puzzle {
+    rope: Vec<Point>,
+    motions: Vec<Motion>,
+    visited: HashSet<Point>,
+}
+
+impl Puzzle {
+    fn new(motions: Vec<Motion>) -> Self {
+        Self {
+            motions,
+            ..Self::default()
+        }
+    }
+
+    fn set_rope_length(&mut self, n: usize) {
+        self.rope = vec![Point::default(); n];
+    }
+
+    fn r#move(&mut self) {
+        for m in self.motions.iter() {
+            for _ in 0..m.num {
+                self.rope[0] += m.dir.vector();
+                for i in 1..self.rope.len() {
+                    let (ahead, knot) = (self.rope[i - 1], self.rope[i]);
+                    if ahead.chebyshev(knot) >= 2 {
+                        self.rope[i] += (ahead - knot).signum();
+                    }
+                    self.visited.insert(*self.rope.last().unwrap());
+                }
+            }
+        }
+    }
+
+    fn count_visited(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+impl FromFile for Puzzle {
+    /// Parse the input file into Puzzle.
+    fn parse(input: Input) -> Result<Self> {
+        let moves = input
+            .lines()
+            .map(|l| {
+                let (dir, num) = l
+                    .split_once(' ')
+                    .ok_or_else(|| input.error(&l[l.len()..], "a space"))?;
+                let dir = dir
+                    .parse()
+                    .wrap_err_with(|| input.error(dir, "one of R, L, U or D"))?;
+                let num = num
+                    .parse()
+                    .wrap_err_with(|| input.error(num, "a number of steps"))?;
+                Ok(Motion { dir, num })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::new(moves))
+    }
+}
+
+impl Solution for Puzzle {
+    type Output = usize;
+
+    /// Solution for part 1.
+    fn solve1(mut self) -> Result<Self::Output> {
+        self.set_rope_length(2);
+        self.r#move();
+        Ok(self.count_visited())
+    }
+
+    /// Solution for part 2.
+    fn solve2(mut self) -> Result<Self::Output> {
+        self.set_rope_length(10);
+        self.r#move();
+        Ok(self.count_visited())
+    }
+}
+
+aoc_tests!(day = 9);
@@ -1,37 +1,599 @@
 use clap::Parser;
-use color_eyre::eyre::{bail, Result};
-use seq_macro::seq;
+use color_eyre::eyre::{bail, eyre, Report, Result, WrapErr};
+use std::{fs, ops::RangeInclusive, path::Path};
 
-use crate::solutions::utils::{FromFile, Solution};
+use crate::config;
+use crate::solutions::{
+    self,
+    utils::{NotImplemented, Parse, Solve, Source},
+};
 use crate::utils;
 
+/// Get the parser of a day's puzzle of the configured year, failing if the day has no solution
+/// implemented.
+pub fn parser(day: u8) -> Result<Parse> {
+    let year = config::get().year;
+    solutions::DAYS
+        .iter()
+        .find(|d| d.year == year && d.day == day)
+        .map(|d| d.parse)
+        .ok_or(eyre!(
+            "Solution for day {day} of {year} not yet implemented"
+        ))
+}
+
+/// Days of the configured year that have a solution implemented.
+fn days() -> impl Iterator<Item = u8> {
+    let year = config::get().year;
+    solutions::DAYS
+        .iter()
+        .filter(move |d| d.year == year)
+        .map(|d| d.day)
+}
+
+/// Directory holding the testdata of a day of a year.
+pub fn year_day_dir(year: u16, day: u8) -> String {
+    format!(
+        "{}/{year}/day_{}",
+        config::get().testdata_dir,
+        utils::fmt_day(day)
+    )
+}
+
+/// Directory holding the testdata of a day of the configured year.
+pub fn day_dir(day: u8) -> String {
+    year_day_dir(config::get().year, day)
+}
+
+/// Directory holding the testdata of a stage (e.g. `sample` or `puzzle`) of a day.
+pub fn stage_dir(day: u8, stage: &str) -> String {
+    format!("{}/{stage}", day_dir(day))
+}
+
+pub fn input_path(day: u8, stage: &str) -> String {
+    format!("{}/input.txt", stage_dir(day, stage))
+}
+
+/// File holding the expected answer of a part in a case directory.
+pub fn output_path(dir: &str, part: u8) -> String {
+    format!("{dir}/output-part{part}.txt")
+}
+
+/// Directories of the cases of a stage directory: the directory itself if it holds an
+/// `input.txt`, followed by its numbered `case-N/` subdirectories in order.
+///
+/// Each case has an `input.txt` and optionally the expected `output-partN.txt` of each part.
+pub fn cases(dir: &str) -> Vec<String> {
+    let mut numbered: Vec<(u32, String)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let n = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("case-")?
+                .parse()
+                .ok()?;
+            path.join("input.txt")
+                .exists()
+                .then(|| (n, path.to_string_lossy().into_owned()))
+        })
+        .collect();
+    numbered.sort();
+
+    let mut cases = vec![];
+    if Path::new(dir).join("input.txt").exists() {
+        cases.push(dir.to_string());
+    }
+    cases.extend(numbered.into_iter().map(|(_, dir)| dir));
+    cases
+}
+
+/// Select the days to run.
+#[derive(clap::Args, Debug)]
+pub struct Days {
+    /// Specify the day of the puzzle.
+    #[arg(
+        value_parser = clap::value_parser!(u8).range(1..=25),
+        required_unless_present_any = ["all", "days"],
+    )]
+    day: Option<u8>,
+    /// Run every implemented day.
+    #[arg(short, long, conflicts_with_all = ["day", "days"])]
+    all: bool,
+    /// Run every implemented day in a range, using Rust range syntax: `3..7` runs days 3 to 6,
+    /// `3..=7` runs days 3 to 7.
+    #[arg(long, value_parser = parse_days, conflicts_with = "day")]
+    days: Option<RangeInclusive<u8>>,
+}
+
+/// Parse a range of days in the form of `a..b` (excluding `b`) or `a..=b` (including `b`).
+fn parse_days(s: &str) -> Result<RangeInclusive<u8>> {
+    let (start, end) = s.split_once("..").ok_or(eyre!(
+        "Expected a range of days like `3..7` or `3..=7`, got `{s}`"
+    ))?;
+    let start: u8 = start
+        .parse()
+        .wrap_err(format!("Invalid start day `{start}` in range `{s}`"))?;
+    let end: u8 = match end.strip_prefix('=') {
+        Some(end) => end.parse(),
+        None => end.parse().map(|end: u8| end.saturating_sub(1)),
+    }
+    .wrap_err(format!("Invalid end day `{end}` in range `{s}`"))?;
+    if !(1..=25).contains(&start) || !(1..=25).contains(&end) || start > end {
+        bail!("`{s}` is not a non-empty range of days within 1..=25");
+    }
+    Ok(start..=end)
+}
+
+impl Days {
+    /// The single day asked for, if not running several days.
+    pub fn single(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// Implemented days selected by `--all` or `--days`.
+    pub fn selected(&self) -> Result<Vec<u8>> {
+        let days: Vec<u8> = match (&self.days, self.day) {
+            (Some(range), _) => days().filter(|d| range.contains(d)).collect(),
+            (None, Some(day)) => vec![day],
+            (None, None) => days().collect(),
+        };
+        if days.is_empty() {
+            bail!("No implemented day to run in {}", config::get().year);
+        }
+        Ok(days)
+    }
+}
+
+/// Outcome of running a day as part of a multi-day run.
+pub enum Status {
+    Done,
+    Skipped(String),
+    /// Some parts are not yet implemented, the others succeeded.
+    Unimplemented(Vec<u8>),
+    Failed(Report),
+}
+
+/// Run `f` on each day, skipping days without any case for the stage, and print a summary at the
+/// end.
+///
+/// Fails if any of the days failed.
+pub fn run_days<F>(days: Vec<u8>, stage: &str, mut f: F) -> Result<()>
+where
+    F: FnMut(u8) -> Result<Status>,
+{
+    let mut statuses = vec![];
+    for day in days {
+        let dir = stage_dir(day, stage);
+        let status = if cases(&dir).is_empty() {
+            println!(
+                "[!] Skipping day {}: no input found in `{dir}`.",
+                utils::fmt_day(day)
+            );
+            Status::Skipped(format!("no input found in `{dir}`"))
+        } else {
+            match f(day) {
+                Ok(status) => status,
+                Err(e) => {
+                    println!("\n[Error] {e:#}");
+                    Status::Failed(e)
+                }
+            }
+        };
+        println!();
+        statuses.push((day, status));
+    }
+
+    println!("================ Summary ================");
+    for (day, status) in &statuses {
+        let day = utils::fmt_day(*day);
+        match status {
+            Status::Done => println!("Day {day}  OK"),
+            Status::Skipped(reason) => println!("Day {day}  SKIPPED  {reason}"),
+            Status::Unimplemented(parts) => {
+                println!("Day {day}  TODO     part {parts:?} not yet implemented")
+            }
+            Status::Failed(e) => println!("Day {day}  FAILED   {e:#}"),
+        }
+    }
+
+    let failed = statuses
+        .iter()
+        .filter(|(_, s)| matches!(s, Status::Failed(_)))
+        .count();
+    if failed > 0 {
+        bail!("{failed} of {} days failed", statuses.len());
+    }
+    Ok(())
+}
+
 /// Run the solution of a day.
 ///
-/// Run solutions for both part 1 and 2.
+/// Run solutions for both part 1 and 2 unless `--part` picks one of them.
 #[derive(Parser, Debug)]
 pub struct Args {
-    /// Specify the day of the puzzle.
-    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
-    day: u8,
+    #[command(flatten)]
+    days: Days,
+    /// Testdata stage to read input from and save answers to, e.g. `sample`, `puzzle`
+    /// [default: puzzle, or `stage` in the configuration].
+    #[arg(long)]
+    stage: Option<String>,
+    /// Read input from a file instead, `-` for stdin.
+    #[arg(
+        short,
+        long,
+        conflicts_with_all = ["stage", "all", "days", "save", "check"],
+    )]
+    input: Option<Source>,
     /// Save the puzzle answers to testdata.
     #[arg(short, long)]
     save: bool,
+    /// Overwrite saved answers that differ from the computed ones.
+    #[arg(short, long, requires = "save")]
+    force: bool,
+    /// Check the answers against the ones saved in testdata instead of printing only, the
+    /// default when `check` is set in the configuration and not saving.
+    #[arg(short, long, conflicts_with = "save")]
+    check: bool,
+    /// Run only one part of the puzzle.
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+}
+
+/// Read a saved answer, returning `None` if it is missing or still the placeholder.
+pub fn read_saved(path: &str) -> Result<Option<String>> {
+    if !Path::new(path).exists() {
+        return Ok(None);
+    }
+    let saved = utils::read_file(path)?.trim().to_string();
+    Ok((saved != utils::PLACEHOLDER).then_some(saved))
+}
+
+/// Result of checking an answer against the saved one.
+#[derive(Debug, PartialEq, Eq)]
+enum Check {
+    Pass,
+    /// Mismatch, with the saved answer.
+    Fail(String),
+    /// No answer saved yet.
+    Unknown,
+}
+
+impl Check {
+    fn new(answer: &str, saved: Option<String>) -> Self {
+        match saved {
+            Some(saved) if saved == answer => Self::Pass,
+            Some(saved) => Self::Fail(saved),
+            None => Self::Unknown,
+        }
+    }
+}
+
+/// Save answers to their `(path, answer)` files.
+///
+/// Nothing is written if any of the files holds a different known answer, unless `force` is set.
+fn save_answers(answers: &[(String, String)], force: bool) -> Result<()> {
+    if !force {
+        for (path, answer) in answers {
+            if let Check::Fail(saved) = Check::new(answer, read_saved(path)?) {
+                bail!(
+                    "Refusing to overwrite saved answer {saved} in {path}, \
+                    use --force to overwrite"
+                );
+            }
+        }
+    }
+    for (path, answer) in answers {
+        utils::write_file(path.as_str(), answer)?;
+        println!("(Saved to {path})");
+    }
+    Ok(())
+}
+
+impl Args {
+    fn stage(&self) -> &str {
+        let default = config::get().defaults.stage.as_deref();
+        self.stage.as_deref().or(default).unwrap_or("puzzle")
+    }
+
+    fn check(&self) -> bool {
+        self.check || (config::get().defaults.check && !self.save && self.input.is_none())
+    }
+
+    /// Parse the puzzle input of each case of a day and run its solution.
+    fn solve_day(&self, day: u8) -> Result<Status> {
+        let parse = parser(day)?;
+        let parts = match self.part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        let dir = stage_dir(day, self.stage());
+        let cases = match &self.input {
+            Some(source) => vec![(source.clone(), None)],
+            None => cases(&dir)
+                .into_iter()
+                .map(|case| (Source::File(format!("{case}/input.txt")), Some(case)))
+                .collect(),
+        };
+        if cases.is_empty() {
+            bail!("No input found in `{dir}`");
+        }
+
+        println!(
+            "================ Day {} ================",
+            utils::fmt_day(day)
+        );
+        let mut failed = vec![];
+        let mut unimplemented = vec![];
+        for (source, case) in &cases {
+            // Name the case when it is one of several in `case-N/` subdirectories.
+            let name = case
+                .as_deref()
+                .and_then(|case| case.strip_prefix(&format!("{dir}/")));
+            if let Some(name) = name {
+                println!("---------------- {name} ----------------");
+            }
+            let parts = match parse(source) {
+                Err(e) if e.is::<NotImplemented>() => {
+                    println!("[!] Parsing not yet implemented");
+                    return Ok(Status::Unimplemented(parts));
+                }
+                puzzle => self.solve_case(puzzle?.as_ref(), case.as_deref(), &parts)?,
+            };
+            if !parts.failed.is_empty() {
+                failed.push(match name {
+                    Some(name) => format!("{name} part {:?}", parts.failed),
+                    None => format!("part {:?}", parts.failed),
+                });
+            }
+            unimplemented.extend(parts.unimplemented);
+        }
+
+        if !failed.is_empty() {
+            bail!("Saved answers do not match for {}", failed.join(", "));
+        }
+        if !unimplemented.is_empty() {
+            unimplemented.sort();
+            unimplemented.dedup();
+            return Ok(Status::Unimplemented(unimplemented));
+        }
+        Ok(Status::Done)
+    }
+
+    /// Run the solution of each part on a parsed puzzle, checking or saving the answers in the
+    /// case directory if any.
+    fn solve_case(&self, puzzle: &dyn Solve, dir: Option<&str>, parts: &[u8]) -> Result<Parts> {
+        let mut result = Parts::default();
+        let mut answers = vec![];
+        for (i, &part) in parts.iter().enumerate() {
+            if i != 0 {
+                println!();
+            }
+            print!("[Part {part}] ");
+            let answer = match puzzle.solve_part(part) {
+                Err(e) if e.is::<NotImplemented>() => {
+                    println!("Not yet implemented");
+                    result.unimplemented.push(part);
+                    continue;
+                }
+                answer => answer?,
+            };
+            println!("Answer: {answer}");
+
+            let Some(dir) = dir else {
+                continue;
+            };
+            let path = output_path(dir, part);
+            if self.check() {
+                match Check::new(&answer, read_saved(&path)?) {
+                    Check::Pass => println!("[Check] PASS"),
+                    Check::Fail(saved) => {
+                        println!("[Check] FAIL: expected {saved}");
+                        result.failed.push(part);
+                    }
+                    Check::Unknown => println!("[Check] UNKNOWN: no saved answer in {path}"),
+                }
+            }
+            answers.push((path, answer));
+        }
+
+        if self.save {
+            save_answers(&answers, self.force)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Parts of a case that did not succeed.
+#[derive(Default)]
+struct Parts {
+    /// Answer does not match the saved one.
+    failed: Vec<u8>,
+    unimplemented: Vec<u8>,
 }
 
 impl utils::Cmd for Args {
     async fn run(self) -> Result<()> {
-        seq!(D in 01..=09 {
-            match self.day {
-                #(D => {
-                    use crate::solutions::day_~D::Puzzle;
-                    let path = format!("testdata/day_{}/puzzle/input.txt", stringify!(D));
-                    let puzzle = Puzzle::from_file(path.as_str())?;
-                    puzzle.solve(D, self.save)
-                })*
-                d => {
-                    bail!("Solution for day {d} not yet implemented")
-                }
+        match self.days.single() {
+            Some(day) => self.solve_day(day).map(|_| ()),
+            None => run_days(self.days.selected()?, self.stage(), |day| {
+                self.solve_day(day)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use color_eyre::eyre::{eyre, Result};
+    use std::ops::RangeInclusive;
+    use test_case::test_case;
+
+    use super::{
+        cases, output_path, parse_days, read_saved, run_days, save_answers, Args, Check, Days,
+        Status,
+    };
+    use crate::config;
+    use crate::solutions::utils::{FromFile, Input};
+    use crate::solutions::y2022::day_01::Puzzle;
+    use crate::solutions::DAYS;
+
+    /// Write `content` to a fresh file in a temporary directory unique to `name`.
+    fn temp_file(name: &str, content: Option<&str>) -> String {
+        let dir = std::env::temp_dir().join(format!("aoc22-solve-{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("output.txt");
+        if let Some(content) = content {
+            std::fs::write(&path, content).unwrap();
+        }
+        path.display().to_string()
+    }
+
+    #[test_case(None, None ; "missing")]
+    #[test_case(Some("0\n"), None ; "placeholder")]
+    #[test_case(Some("157\n"), Some("157") ; "answer")]
+    fn saved(content: Option<&str>, expected: Option<&str>) {
+        let path = temp_file(&format!("saved-{content:?}"), content);
+        assert_eq!(read_saved(&path).unwrap().as_deref(), expected);
+    }
+
+    #[test_case("157", Some("157"), Check::Pass ; "pass")]
+    #[test_case("157", Some("999"), Check::Fail("999".into()) ; "fail")]
+    #[test_case("157", None, Check::Unknown ; "unknown")]
+    fn check(answer: &str, saved: Option<&str>, expected: Check) {
+        assert_eq!(Check::new(answer, saved.map(Into::into)), expected);
+    }
+
+    #[test_case(false, "0", "999" ; "refused")]
+    #[test_case(true, "1", "2" ; "forced")]
+    fn save_guard(force: bool, expected1: &str, expected2: &str) {
+        let path1 = temp_file(&format!("guard1-{force}"), Some("0"));
+        let path2 = temp_file(&format!("guard2-{force}"), Some("999"));
+        let answers = [(path1.clone(), "1".into()), (path2.clone(), "2".into())];
+        assert_eq!(save_answers(&answers, force).is_ok(), force);
+
+        // Part 1 must not be written when part 2 is refused.
+        assert_eq!(std::fs::read_to_string(path1).unwrap(), expected1);
+        assert_eq!(std::fs::read_to_string(path2).unwrap(), expected2);
+    }
+
+    #[test_case("3..7", Some(3..=6) ; "exclusive")]
+    #[test_case("3..=7", Some(3..=7) ; "inclusive")]
+    #[test_case("7..3", None ; "reversed")]
+    #[test_case("0..2", None ; "out of range")]
+    #[test_case("3..3", None ; "empty")]
+    #[test_case("abc", None ; "not a range")]
+    #[test_case("a..7", None ; "invalid start")]
+    fn days_range(s: &str, expected: Option<RangeInclusive<u8>>) {
+        assert_eq!(parse_days(s).ok(), expected);
+    }
+
+    #[test]
+    fn days_range_error_mentions_input() {
+        let err = parse_days("x..=7").unwrap_err();
+        assert!(format!("{err:#}").contains("`x..=7`"));
+    }
+
+    #[test_case(Some(3..=5), false, Some(vec![3, 4, 5]) ; "range")]
+    #[test_case(Some(20..=25), false, None ; "range without implemented days")]
+    fn days_selected(days: Option<RangeInclusive<u8>>, all: bool, expected: Option<Vec<u8>>) {
+        let days = Days {
+            day: None,
+            all,
+            days,
+        };
+        assert_eq!(days.selected().ok(), expected);
+    }
+
+    #[test]
+    fn all_days_selected() {
+        let year = config::get().year;
+        let implemented: Vec<u8> = DAYS
+            .iter()
+            .filter(|d| d.year == year)
+            .map(|d| d.day)
+            .collect();
+        let days = Days {
+            day: None,
+            all: true,
+            days: None,
+        };
+        assert_eq!(days.selected().unwrap(), implemented);
+    }
+
+    #[test_case(&[], true ; "all done")]
+    #[test_case(&[2], false ; "one failed")]
+    fn run_days_status(failing: &[u8], ok: bool) {
+        let result = run_days(vec![1, 2, 3], "sample", |day| -> Result<Status> {
+            if failing.contains(&day) {
+                Err(eyre!("failed"))
+            } else if day == 3 {
+                Ok(Status::Unimplemented(vec![2]))
+            } else {
+                Ok(Status::Done)
             }
-        })
+        });
+        assert_eq!(result.is_ok(), ok);
+    }
+
+    #[test]
+    fn run_days_skips_missing_input() {
+        let mut ran = vec![];
+        let result = run_days(vec![1, 2], "no-such-stage", |day| {
+            ran.push(day);
+            Ok(Status::Done)
+        });
+        assert!(result.is_ok());
+        assert!(ran.is_empty());
+    }
+
+    #[test]
+    fn answers_under_absolute_testdata_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc22-solve-abs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let dir = dir.join("2022/day_01/sample");
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = dir.to_str().unwrap();
+        assert!(dir.starts_with('/'));
+        assert_eq!(output_path(dir, 1), format!("{dir}/output-part1.txt"));
+
+        let puzzle = Puzzle::parse(Input::new("1\n2\n\n4\n\n5\n")).unwrap();
+        let save = Args::try_parse_from(["solve", "1", "--save"]).unwrap();
+        save.solve_case(&puzzle, Some(dir), &[1, 2]).unwrap();
+        assert_eq!(
+            read_saved(&output_path(dir, 1)).unwrap().as_deref(),
+            Some("5")
+        );
+        assert_eq!(
+            read_saved(&output_path(dir, 2)).unwrap().as_deref(),
+            Some("12")
+        );
+
+        let check = Args::try_parse_from(["solve", "1", "--check"]).unwrap();
+        let parts = check.solve_case(&puzzle, Some(dir), &[1, 2]).unwrap();
+        assert!(parts.failed.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cases_in_order() {
+        let dir = std::env::temp_dir().join("aoc22-solve-cases");
+        let _ = std::fs::remove_dir_all(&dir);
+        for case in ["", "case-2", "case-10", "case-1", "notes"] {
+            std::fs::create_dir_all(dir.join(case)).unwrap();
+            std::fs::write(dir.join(case).join("input.txt"), "").unwrap();
+        }
+        std::fs::create_dir_all(dir.join("case-3")).unwrap();
+
+        let dir = dir.to_string_lossy();
+        let expected: Vec<String> = ["", "/case-1", "/case-2", "/case-10"]
+            .iter()
+            .map(|case| format!("{dir}{case}"))
+            .collect();
+        assert_eq!(cases(&dir), expected);
     }
 }
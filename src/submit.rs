@@ -0,0 +1,196 @@
+use clap::Parser;
+use color_eyre::eyre::{bail, ensure, eyre, Result, WrapErr};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::{Html, Selector};
+use std::{
+    fmt::{self, Display},
+    fs,
+};
+
+use crate::client::Client;
+use crate::history::History;
+use crate::solutions::utils::Source;
+use crate::solve;
+use crate::utils;
+
+static WAIT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+
+/// Submit the answer of a puzzle.
+///
+/// Compute the answer from the puzzle input unless given, and save it to testdata when correct.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// Specify the day of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Specify the part of the puzzle.
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Answer to submit instead of the one computed from the puzzle input.
+    answer: Option<String>,
+}
+
+/// Outcome of a submission, as told by the response page.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong without a hint on which way.
+    Wrong,
+    /// Submitted too recently, with the number of seconds left to wait.
+    Wait(u64),
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Parse the verdict from the response page of a submission.
+    pub fn from_page(html: &str) -> Result<Self> {
+        let doc = Html::parse_document(html);
+        let sel = Selector::parse("main > article").unwrap();
+        let text: String = doc
+            .select(&sel)
+            .next()
+            .ok_or(eyre!("Can't find response article element"))?
+            .text()
+            .collect();
+
+        let verdict = if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("your answer is too high") {
+            Self::TooHigh
+        } else if text.contains("your answer is too low") {
+            Self::TooLow
+        } else if text.contains("That's not the right answer") {
+            Self::Wrong
+        } else if text.contains("You gave an answer too recently") {
+            let captures = WAIT_RE
+                .captures(&text)
+                .ok_or(eyre!("Can't find time left to wait in: {text}"))?;
+            let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse())?;
+            let seconds: u64 = captures[2].parse()?;
+            Self::Wait(minutes * 60 + seconds)
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            bail!("Unknown response: {}", text.trim())
+        };
+        Ok(verdict)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "Correct"),
+            Self::TooHigh => write!(f, "Too high"),
+            Self::TooLow => write!(f, "Too low"),
+            Self::Wrong => write!(f, "Wrong"),
+            Self::Wait(secs) => write!(f, "Wait {secs} seconds"),
+            Self::AlreadySolved => write!(f, "Already solved"),
+        }
+    }
+}
+
+impl Args {
+    /// Compute the answer from the puzzle input.
+    fn compute(&self) -> Result<String> {
+        let parse = solve::parser(self.day)?;
+        let puzzle = parse(&Source::File(solve::input_path(self.day, "puzzle")))?;
+        puzzle.solve_part(self.part)
+    }
+
+    /// Submit an answer and parse the verdict from the response.
+    async fn submit(&self, client: &Client, answer: &str) -> Result<Verdict> {
+        let page = client.submit(self.day, self.part, answer).await?;
+        Verdict::from_page(&page)
+    }
+}
+
+impl utils::Cmd for Args {
+    async fn run(self) -> Result<()> {
+        let answer = match &self.answer {
+            Some(answer) => answer.trim().to_string(),
+            None => self.compute()?,
+        };
+        ensure!(
+            !answer.is_empty() && answer != utils::PLACEHOLDER,
+            "Refusing to submit `{answer}`, which is empty or the placeholder answer"
+        );
+        let mut history = History::load(self.day)?;
+        history
+            .check(self.part, &answer)
+            .wrap_err("Refusing to submit a known wrong answer")?;
+
+        println!(
+            "[-] Submitting {answer} for day {} part {}.",
+            utils::fmt_day(self.day),
+            self.part
+        );
+        let verdict = self.submit(&Client::new()?, &answer).await?;
+        println!("[Verdict] {verdict}");
+
+        history.record(self.part, &answer, &verdict);
+        history.save(self.day)?;
+
+        match verdict {
+            Verdict::Correct => {
+                // The answer is accepted at this point, so only warn if it can't be saved.
+                let dir = solve::stage_dir(self.day, "puzzle");
+                let path = solve::output_path(&dir, self.part);
+                match fs::create_dir_all(&dir)
+                    .map_err(Into::into)
+                    .and_then(|_| utils::write_file(path.as_str(), &answer))
+                {
+                    Ok(()) => println!("(Saved to {path})"),
+                    Err(e) => println!("[!] Failed to save answer {answer} to {path}: {e:#}"),
+                }
+                Ok(())
+            }
+            Verdict::AlreadySolved => Ok(()),
+            verdict => bail!("Answer {answer} was not accepted: {verdict}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_case::test_case;
+
+    use super::{Args, Verdict};
+    use crate::client::{Client, USER_AGENT};
+    use crate::fake_server::FakeServer;
+
+    #[test_case("That's the right answer!  You are <span>one gold star</span> closer.", Verdict::Correct ; "correct")]
+    #[test_case("That's not the right answer; your answer is too high.  Please wait one minute before trying again.", Verdict::TooHigh ; "too high")]
+    #[test_case("That's not the right answer; your answer is too low.", Verdict::TooLow ; "too low")]
+    #[test_case("That's not the right answer.  If you're stuck, make sure you're using the full input data.", Verdict::Wrong ; "wrong")]
+    #[test_case("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait.", Verdict::Wait(41) ; "wait seconds")]
+    #[test_case("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 3s left to wait.", Verdict::Wait(243) ; "wait minutes")]
+    #[test_case("You don't seem to be solving the right level.  Did you already complete it?", Verdict::AlreadySolved ; "already solved")]
+    fn verdict(text: &str, expected: Verdict) {
+        let page =
+            format!("<html><body><main><article><p>{text}</p></article></main></body></html>");
+        assert_eq!(Verdict::from_page(&page).unwrap(), expected);
+    }
+
+    #[test_case("answer_correct.html", Verdict::Correct ; "correct")]
+    #[test_case("answer_too_low.html", Verdict::TooLow ; "too low")]
+    #[tokio::test]
+    async fn submit(fixture: &str, expected: Verdict) {
+        let server = FakeServer::start(&[("POST", "/2022/day/1/answer", fixture)]);
+        let client = Client::with_url(&server.url, Some("abc".into()), USER_AGENT).unwrap();
+        let args = Args {
+            day: 1,
+            part: 1,
+            answer: None,
+        };
+        assert_eq!(args.submit(&client, "24000").await.unwrap(), expected);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].body, "level=1&answer=24000");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc"));
+    }
+}
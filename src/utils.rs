@@ -2,6 +2,9 @@ use clap::Parser;
 use color_eyre::eyre::{Result, WrapErr};
 use std::{fmt::Display, fs, path::Path};
 
+/// Content of an output file whose answer is not known yet.
+pub const PLACEHOLDER: &str = "0";
+
 pub trait Cmd: Parser + Sized {
     async fn run(self) -> Result<()>;
 }
@@ -22,7 +25,7 @@ where
 }
 
 pub fn fmt_day(day: u8) -> String {
-    format!("{:0>2}", day)
+    format!("{day:0>2}")
 }
 
 pub fn capitalize(s: &str) -> String {